@@ -0,0 +1,44 @@
+// Benchmarks for parsing and both parts of every day, using criterion.
+//
+//     cargo bench -p aoc                 # everything
+//     cargo bench -p aoc -- day7/        # only day 7
+//
+// To compare against another commit, save a baseline there first and compare against it later:
+//
+//     cargo bench -p aoc -- --save-baseline main
+//     cargo bench -p aoc -- --baseline main
+
+use std::hint::black_box;
+
+use common::Solution;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_day<S: Solution>(c: &mut Criterion, number: u8, input: &str) {
+    let mut group = c.benchmark_group(format!("day{number}"));
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(input)).unwrap()));
+    // The parts are measured on the already parsed input, like the runner does it.
+    let parsed = S::parse(input).unwrap();
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    group.finish();
+}
+
+fn bench_days(c: &mut Criterion) {
+    bench_day::<day1::Day1>(c, 1, day1::INPUT);
+    bench_day::<day2::Day2>(c, 2, day2::INPUT);
+    bench_day::<day3::Day3>(c, 3, day3::INPUT);
+    bench_day::<day4::Day4>(c, 4, day4::INPUT);
+    bench_day::<day5::Day5>(c, 5, day5::INPUT);
+    bench_day::<day6::Day6>(c, 6, day6::INPUT);
+    bench_day::<day7::Day7>(c, 7, day7::INPUT);
+    bench_day::<day8::Day8>(c, 8, day8::INPUT);
+    bench_day::<day9::Day9>(c, 9, day9::INPUT);
+    bench_day::<day10::Day10>(c, 10, day10::INPUT);
+    bench_day::<day11::Day11>(c, 11, day11::INPUT);
+    bench_day::<day12::Day12>(c, 12, day12::INPUT);
+    bench_day::<day13::Day13>(c, 13, day13::INPUT);
+    bench_day::<day14::Day14>(c, 14, day14::INPUT);
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
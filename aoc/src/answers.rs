@@ -0,0 +1,124 @@
+// A local store of the accepted answers for every day and part, so the runner can check that the
+// solutions still produce them (`--check`). It's a JSON file that looks like this:
+//
+//     {
+//       "1": {
+//         "1": "70764",
+//         "2": "203905"
+//       },
+//       ...
+//     }
+//
+// and is written by running with `--save`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PATH: &str = "answers.json";
+
+pub struct Answers {
+    path: PathBuf,
+    // A BTreeMap keeps the days and parts sorted, so the file stays in order when it's saved.
+    answers: BTreeMap<u8, BTreeMap<u8, String>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Correct,
+    Wrong { expected: String },
+    // There is no known answer for this day and part yet.
+    Unknown,
+}
+
+impl Answers {
+    // A missing file is fine, it's the same as a file without any answers.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let answers = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(Answers {
+            path: path.to_path_buf(),
+            answers,
+        })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(&self.answers)?;
+        fs::write(&self.path, content + "\n")
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&day)?.get(&part).map(|s| s.as_str())
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: &str) {
+        self.answers
+            .entry(day)
+            .or_default()
+            .insert(part, answer.to_string());
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Check {
+        match self.get(day, part) {
+            None => Check::Unknown,
+            Some(expected) if expected == answer => Check::Correct,
+            Some(expected) => Check::Wrong {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+// Shows the difference between the expected and the actual answer. For answers with a single
+// line that's just both of them, for multiple lines (day 10) only the lines that differ are marked
+// with `-` (expected) and `+` (actual).
+pub fn diff(expected: &str, actual: &str) -> String {
+    if !expected.contains('\n') && !actual.contains('\n') {
+        return format!("  expected: {expected}\n  actual:   {actual}");
+    }
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut result = vec![];
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => result.push(format!("  {e}")),
+            (e, a) => {
+                if let Some(e) = e {
+                    result.push(format!("- {e}"));
+                }
+                if let Some(a) = a {
+                    result.push(format!("+ {a}"));
+                }
+            }
+        }
+    }
+    result.join("\n")
+}
+
+#[test]
+fn test_check() {
+    let mut answers = Answers {
+        path: PathBuf::new(),
+        answers: BTreeMap::new(),
+    };
+    answers.set(7, 1, "1307902");
+    assert_eq!(answers.check(7, 1, "1307902"), Check::Correct);
+    assert_eq!(
+        answers.check(7, 1, "1307903"),
+        Check::Wrong {
+            expected: "1307902".to_string()
+        }
+    );
+    assert_eq!(answers.check(7, 2, "7068748"), Check::Unknown);
+}
+
+#[test]
+fn test_diff() {
+    assert_eq!(diff("1", "2"), "  expected: 1\n  actual:   2");
+    assert_eq!(diff("#.\n.#\n", "#.\n##\n"), "  #.\n- .#\n+ ##");
+    assert_eq!(diff("a\nb", "a"), "  a\n- b");
+}
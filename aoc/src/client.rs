@@ -0,0 +1,258 @@
+// Talks to the Advent of Code website, or anything that behaves like it (like the mock server in
+// the tests, see `mock.rs`).
+//
+// The puzzle inputs are different for every user, so you have to be logged in to download them.
+// The session token is the value of the `session` cookie on adventofcode.com. Pass it with
+// `--session` or set the `AOC_SESSION` environment variable. `--base-url` (or `AOC_BASE_URL`)
+// points the client to another server.
+//
+// Downloaded inputs are cached on disk (in `.aoc/` by default), so every input is only downloaded
+// once. To be nice to the server, there is also a minimum time between two requests (5 seconds by
+// default), which is remembered across runs.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::slice;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_YEAR: u16 = 2022;
+pub const DEFAULT_CACHE_DIR: &str = ".aoc";
+pub const DEFAULT_RATE_LIMIT: Duration = Duration::from_secs(5);
+
+// The Advent of Code website asks automated tools to identify themselves.
+const USER_AGENT: &str = "github.com/trendels/aoc2022";
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub year: u16,
+    pub cache_dir: PathBuf,
+    pub rate_limit: Duration,
+}
+
+impl Config {
+    // The defaults, with the session token and base URL taken from the environment if they are set.
+    pub fn from_env() -> Self {
+        Config {
+            base_url: std::env::var("AOC_BASE_URL").unwrap_or(DEFAULT_BASE_URL.to_string()),
+            session: std::env::var("AOC_SESSION").ok(),
+            year: DEFAULT_YEAR,
+            cache_dir: PathBuf::from(DEFAULT_CACHE_DIR),
+            rate_limit: DEFAULT_RATE_LIMIT,
+        }
+    }
+
+    // Handles one of the command line options shared by all commands that talk to the website.
+    // Returns `false` if `arg` isn't one of them.
+    pub fn parse_option(
+        &mut self,
+        arg: &str,
+        args: &mut slice::Iter<String>,
+    ) -> Result<bool, String> {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        match arg {
+            "--year" => {
+                let year = value()?;
+                self.year = year.parse().map_err(|_| format!("invalid year: {year}"))?;
+            }
+            "--session" => self.session = Some(value()?.clone()),
+            "--base-url" => self.base_url = value()?.clone(),
+            "--cache-dir" => self.cache_dir = PathBuf::from(value()?),
+            "--rate-limit" => {
+                let seconds = value()?;
+                let seconds: f64 = seconds
+                    .parse()
+                    .map_err(|_| format!("invalid rate limit: {seconds}"))?;
+                self.rate_limit = Duration::from_secs_f64(seconds);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    MissingSession,
+    // The server answered with an error status. Contains the status code and the response body.
+    Status(u16, String),
+    // Something went wrong before we got an answer (connection refused, DNS, TLS, ...).
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingSession => {
+                write!(f, "no session token, use --session or set AOC_SESSION")
+            }
+            Error::Status(404, _) => write!(f, "not found (is the puzzle unlocked yet?)"),
+            Error::Status(code @ (400 | 500), _) => {
+                write!(f, "server error {code} (is the session token still valid?)")
+            }
+            Error::Status(code, body) => write!(f, "server error {code}: {}", body.trim()),
+            Error::Transport(msg) => write!(f, "{msg}"),
+            Error::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+pub struct Client {
+    config: Config,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::AgentBuilder::new().user_agent(USER_AGENT).build();
+        Client { config, agent }
+    }
+
+    // Returns the puzzle input for `day`, from the cache if we downloaded it before.
+    pub fn input(&self, day: u8) -> Result<String, Error> {
+        let path = self.year_dir().join(format!("day{day}.txt"));
+        if let Ok(input) = fs::read_to_string(&path) {
+            return Ok(input);
+        }
+        let input = self.get(&format!("/{}/day/{day}/input", self.config.year))?;
+        fs::create_dir_all(self.year_dir())?;
+        fs::write(&path, &input)?;
+        Ok(input)
+    }
+
+    pub fn year(&self) -> u16 {
+        self.config.year
+    }
+
+    // Everything we store for a year goes into its own directory in the cache.
+    pub fn year_dir(&self) -> PathBuf {
+        self.config.cache_dir.join(self.config.year.to_string())
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, Error> {
+        let request = self.request("GET", path)?;
+        Self::read_response(request.call())
+    }
+
+    // Sends a form (`application/x-www-form-urlencoded`), like the browser does.
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, Error> {
+        let request = self.request("POST", path)?;
+        Self::read_response(request.send_form(form))
+    }
+
+    fn request(&self, method: &str, path: &str) -> Result<ureq::Request, Error> {
+        let session = self.config.session.as_ref().ok_or(Error::MissingSession)?;
+        self.wait_for_rate_limit()?;
+        let url = format!("{}{path}", self.config.base_url.trim_end_matches('/'));
+        Ok(self
+            .agent
+            .request(method, &url)
+            .set("Cookie", &format!("session={session}")))
+    }
+
+    fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, Error> {
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(code, response)) => Err(Error::Status(
+                code,
+                response.into_string().unwrap_or_default(),
+            )),
+            Err(ureq::Error::Transport(err)) => Err(Error::Transport(err.to_string())),
+        }
+    }
+
+    // The time of the last request is stored in the cache directory (in milliseconds since the
+    // epoch), so the rate limit also works across separate runs of the program.
+    fn wait_for_rate_limit(&self) -> io::Result<()> {
+        let path = self.config.cache_dir.join("last_request");
+        let last = fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+        if let Some(last) = last {
+            let next = last + self.config.rate_limit;
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            if next > now {
+                thread::sleep(next - now);
+            }
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        fs::create_dir_all(&self.config.cache_dir)?;
+        fs::write(&path, now.as_millis().to_string())
+    }
+}
+
+#[cfg(test)]
+pub fn test_config(name: &str, base_url: &str) -> Config {
+    let cache_dir = std::env::temp_dir().join(format!("aoc-test-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&cache_dir);
+    Config {
+        base_url: base_url.to_string(),
+        session: Some("s3cr3t".to_string()),
+        year: DEFAULT_YEAR,
+        cache_dir,
+        rate_limit: Duration::ZERO,
+    }
+}
+
+#[test]
+fn test_input_is_cached() {
+    let server = crate::mock::MockServer::start(200, "1\n2\n");
+    let client = Client::new(test_config("cached", &server.url));
+    assert_eq!(client.input(7).unwrap(), "1\n2\n");
+    assert_eq!(client.input(7).unwrap(), "1\n2\n");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /2022/day/7/input HTTP/1.1\r\n"));
+    assert!(requests[0].contains("session=s3cr3t"));
+    assert!(requests[0].contains(USER_AGENT));
+    let cached = client.config.cache_dir.join("2022/day7.txt");
+    assert_eq!(fs::read_to_string(cached).unwrap(), "1\n2\n");
+}
+
+#[test]
+fn test_input_not_found() {
+    let server = crate::mock::MockServer::start(404, "Not Found");
+    let client = Client::new(test_config("not-found", &server.url));
+    assert!(matches!(client.input(25), Err(Error::Status(404, _))));
+    // Errors are not cached.
+    assert!(!client.config.cache_dir.join("2022/day25.txt").exists());
+}
+
+#[test]
+fn test_missing_session() {
+    let server = crate::mock::MockServer::start(200, "");
+    let mut config = test_config("no-session", &server.url);
+    config.session = None;
+    let client = Client::new(config);
+    assert!(matches!(client.input(1), Err(Error::MissingSession)));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn test_rate_limit() {
+    let server = crate::mock::MockServer::start(200, "input");
+    let mut config = test_config("rate-limit", &server.url);
+    config.rate_limit = Duration::from_millis(300);
+    let client = Client::new(config);
+    let start = std::time::Instant::now();
+    client.input(1).unwrap();
+    client.input(2).unwrap();
+    // The time of the last request is stored with millisecond precision, so allow for some
+    // rounding.
+    assert!(start.elapsed() >= Duration::from_millis(290));
+    assert_eq!(server.requests().len(), 2);
+}
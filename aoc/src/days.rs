@@ -0,0 +1,165 @@
+// A table of all solved days, so the runner can look them up by number.
+//
+// Every day crate exposes its bundled `INPUT` and a type implementing `Solution`. The answers have
+// different types (numbers, strings, the CRT screen of day 10), but the runner only needs to print
+// them, so they are converted to strings here.
+
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+use common::{ParseError, Solution, StreamError, StreamSolution};
+
+pub struct Day {
+    pub number: u8,
+    pub input: &'static str,
+    pub solve: fn(&str, &[u8]) -> Result<Report, ParseError>,
+    // For the days that can read the input bit by bit (`aoc run <day> --stream`).
+    pub stream: Option<StreamFn>,
+}
+
+pub type StreamFn = fn(&mut dyn BufRead, &[u8]) -> Result<Report, StreamError>;
+
+pub struct Answer {
+    pub part: u8,
+    pub answer: String,
+    pub time: Duration,
+}
+
+pub struct Report {
+    pub parse_time: Duration,
+    pub answers: Vec<Answer>,
+}
+
+// Parses the input and solves the given parts, and measures how long each step takes.
+fn solve<S: Solution>(input: &str, parts: &[u8]) -> Result<Report, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            // Converting the answer to a string is part of the time, but that should be tiny.
+            let answer = match part {
+                1 => S::part1(&input).to_string(),
+                _ => S::part2(&input).to_string(),
+            };
+            Answer {
+                part,
+                answer,
+                time: start.elapsed(),
+            }
+        })
+        .collect();
+    Ok(Report {
+        parse_time,
+        answers,
+    })
+}
+
+// Reads the input and solves both parts in one go, so there's no separate time for parsing and
+// solving. All of it counts as parse time, the parts take no time after that.
+fn solve_stream<S: StreamSolution>(
+    reader: &mut dyn BufRead,
+    parts: &[u8],
+) -> Result<Report, StreamError> {
+    let start = Instant::now();
+    let (answer1, answer2) = S::solve_stream(reader)?;
+    let parse_time = start.elapsed();
+    let answers = parts
+        .iter()
+        .map(|&part| Answer {
+            part,
+            answer: match part {
+                1 => answer1.to_string(),
+                _ => answer2.to_string(),
+            },
+            time: Duration::ZERO,
+        })
+        .collect();
+    Ok(Report {
+        parse_time,
+        answers,
+    })
+}
+
+macro_rules! day {
+    ($number:literal, $krate:ident, $solution:ident) => {
+        Day {
+            number: $number,
+            input: $krate::INPUT,
+            solve: solve::<$krate::$solution>,
+            stream: None,
+        }
+    };
+    ($number:literal, $krate:ident, $solution:ident, stream) => {
+        Day {
+            number: $number,
+            input: $krate::INPUT,
+            solve: solve::<$krate::$solution>,
+            stream: Some(solve_stream::<$krate::$solution>),
+        }
+    };
+}
+
+pub const DAYS: [Day; 14] = [
+    day!(1, day1, Day1, stream),
+    day!(2, day2, Day2, stream),
+    day!(3, day3, Day3, stream),
+    day!(4, day4, Day4, stream),
+    day!(5, day5, Day5),
+    day!(6, day6, Day6, stream),
+    day!(7, day7, Day7),
+    day!(8, day8, Day8),
+    day!(9, day9, Day9, stream),
+    day!(10, day10, Day10, stream),
+    day!(11, day11, Day11),
+    day!(12, day12, Day12),
+    day!(13, day13, Day13),
+    day!(14, day14, Day14),
+];
+
+pub fn get(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+// A reader that makes up a lot of input as it goes, like the generated inputs for stress tests,
+// without ever having all of it in memory.
+#[cfg(test)]
+struct Repeat {
+    line: &'static [u8],
+    left: usize,
+    pos: usize,
+}
+
+#[cfg(test)]
+impl std::io::Read for Repeat {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut n = 0;
+        while n < buf.len() && self.left > 0 {
+            buf[n] = self.line[self.pos];
+            n += 1;
+            self.pos += 1;
+            if self.pos == self.line.len() {
+                self.pos = 0;
+                self.left -= 1;
+            }
+        }
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_stream_large() {
+    let day2 = get(2).unwrap();
+    let repeat = Repeat {
+        line: b"A Y\n",
+        left: 1_000_000,
+        pos: 0,
+    };
+    let mut reader = std::io::BufReader::new(repeat);
+    let report = (day2.stream.unwrap())(&mut reader, &[1, 2]).unwrap();
+    // Paper against rock wins (2 + 6), and a draw with rock is 1 + 3.
+    assert_eq!(report.answers[0].answer, "8000000");
+    assert_eq!(report.answers[1].answer, "4000000");
+}
@@ -0,0 +1,42 @@
+// `aoc fetch`: downloads the puzzle input for a day and prints it, so it can be redirected to
+// `dayN/input.txt`.
+
+use std::process;
+
+use crate::client::{Client, Config};
+
+pub struct FetchArgs {
+    day: u8,
+    config: Config,
+}
+
+pub fn parse_day(day: Option<&String>) -> Result<u8, String> {
+    let day = day.ok_or("missing day")?;
+    match day.parse() {
+        Ok(number @ 1..=25) => Ok(number),
+        _ => Err(format!("invalid day: {day}")),
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<FetchArgs, String> {
+    let mut args = args.iter();
+    let day = parse_day(args.next())?;
+    let mut config = Config::from_env();
+    while let Some(arg) = args.next() {
+        if !config.parse_option(arg, &mut args)? {
+            return Err(format!("unknown argument: {arg}"));
+        }
+    }
+    Ok(FetchArgs { day, config })
+}
+
+pub fn fetch(args: FetchArgs) {
+    let client = Client::new(args.config);
+    match client.input(args.day) {
+        Ok(input) => print!("{input}"),
+        Err(err) => {
+            eprintln!("error: failed to fetch input for day {}: {err}", args.day);
+            process::exit(1);
+        }
+    }
+}
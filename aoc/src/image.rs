@@ -0,0 +1,115 @@
+// `aoc image`: draws the grid of a day into an image file (see `viz::Image`), for the days that
+// have one:
+//
+// - day 8: the tree heights,
+// - day 10: the CRT screen after running the program,
+// - day 12: the height map with the shortest path of part 1 or 2,
+// - day 14: the cave full of sand after part 1 or 2.
+//
+// The format is taken from the extension of the output path (`.ppm`, `.png` or `.svg`).
+
+use std::path::PathBuf;
+use std::process;
+
+use common::Solution;
+use viz::Image;
+
+use crate::days;
+
+pub struct ImageArgs {
+    day: u8,
+    output: PathBuf,
+    part: u8,
+    input: Option<String>,
+    scale: usize,
+}
+
+pub const DAYS: [u8; 4] = [8, 10, 12, 14];
+
+pub fn parse_args(args: &[String]) -> Result<ImageArgs, String> {
+    let mut args = args.iter();
+    let day = args.next().ok_or("missing day")?;
+    let day = match day.parse() {
+        Ok(number) if DAYS.contains(&number) => number,
+        _ => return Err(format!("no image for day {day}, only for days {DAYS:?}")),
+    };
+    let output = PathBuf::from(args.next().ok_or("missing output path")?);
+    let mut part = 2;
+    let mut input = None;
+    let mut scale = 1;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                part = match value.as_str() {
+                    "1" => 1,
+                    "2" => 2,
+                    _ => return Err(format!("invalid part: {value}")),
+                };
+            }
+            "--input" => input = Some(args.next().ok_or("--input needs a value")?.clone()),
+            "--scale" => {
+                let value = args.next().ok_or("--scale needs a value")?;
+                scale = match value.parse() {
+                    Ok(scale) if scale > 0 => scale,
+                    _ => return Err(format!("invalid scale: {value}")),
+                };
+            }
+            arg => return Err(format!("unknown argument: {arg}")),
+        }
+    }
+    Ok(ImageArgs {
+        day,
+        output,
+        part,
+        input,
+        scale,
+    })
+}
+
+fn parse<S: Solution>(input: &str) -> Result<S::Input, String> {
+    S::parse(input).map_err(|err| format!("failed to parse input: {err}"))
+}
+
+pub fn render(day: u8, input: &str, part: u8) -> Result<Image, String> {
+    match day {
+        8 => Ok(day8::image(&parse::<day8::Day8>(input)?)),
+        10 => Ok(day10::Day10::part2(&parse::<day10::Day10>(input)?).image()),
+        12 => {
+            let map = parse::<day12::Day12>(input)?;
+            let end_value = if part == 1 { 'E' } else { 'a' };
+            let path = day12::find_shortest_path(&map, map.end, map.start, end_value)?;
+            Ok(day12::path_image(&map, &path))
+        }
+        14 => {
+            let (map, _) = day14::pour_sand(&parse::<day14::Day14>(input)?, part);
+            Ok(map.image(part == 2))
+        }
+        _ => Err(format!("no image for day {day}")),
+    }
+}
+
+pub fn image(args: ImageArgs) {
+    let day = days::get(args.day).unwrap();
+    let input = common::input::load_or_exit(args.input.as_deref(), day.input);
+    let image = match render(args.day, &input, args.part) {
+        Ok(image) => image.scale(args.scale),
+        Err(err) => {
+            eprintln!("error: {err}");
+            process::exit(1);
+        }
+    };
+    if let Err(err) = image.save(&args.output) {
+        eprintln!("error: failed to write {}: {err}", args.output.display());
+        process::exit(1);
+    }
+}
+
+#[test]
+fn test_render_all_days() {
+    for number in DAYS {
+        let day = days::get(number).unwrap();
+        let image = render(number, day.input, 2).unwrap();
+        assert!(image.width() > 0 && image.height() > 0);
+    }
+}
@@ -0,0 +1,96 @@
+// A single binary to run the solutions for all days:
+//
+//     aoc run 7            # both parts of day 7
+//     aoc run 7 --part 2   # only part 2
+//     aoc run all          # everything
+//
+// By default each day uses its bundled `input.txt`. Pass `--input <path>` (or `--input -` for
+// stdin) to run a single day against some other input.
+//
+// With `--format json`, one JSON object per day and part is printed instead, with the answer and
+// how long parsing and solving took (see `output.rs`).
+//
+// The accepted answers are stored in `answers.json` (see `answers.rs`). `--check` compares the
+// answers against it and exits with an error if any of them don't match, `--save` stores the
+// answers in it. Use `--answers <path>` to use another file.
+//
+// `aoc run all` solves the days in parallel on `--jobs <n>` threads, gives every day at most
+// `--timeout <seconds>` (30 by default), and ends with a summary table (see `run.rs`).
+//
+// It can also download the puzzle inputs from the Advent of Code website:
+//
+//     aoc fetch 7 > day7/input.txt
+//
+// and submit answers (see `submit.rs`):
+//
+//     aoc submit 7 1
+//
+// See `client.rs` for how to set the session token.
+//
+// `aoc image 12 path.png --scale 4` draws the grid of a day into an image file (see `image.rs`).
+//
+// The solutions themselves live in the `dayN` library crates, the day binaries are still there
+// as well.
+
+use std::env;
+use std::process;
+
+mod answers;
+mod client;
+mod days;
+mod fetch;
+mod image;
+#[cfg(test)]
+mod mock;
+mod output;
+mod parallel;
+mod pool;
+mod run;
+mod submissions;
+mod submit;
+
+const USAGE: &str = "\
+usage: aoc run <day|all> [--part <1|2>] [--input <path|->] [--format <text|json>]
+                         [--check] [--save] [--answers <path>] [--jobs <n>]
+                         [--timeout <seconds>] [--stream]
+       aoc fetch <day> [--year <year>] [--session <token>] [--base-url <url>]
+                       [--cache-dir <path>] [--rate-limit <seconds>]
+       aoc submit <day> <1|2> [--answer <value>] [--input <path|->] [fetch options]
+       aoc image <8|10|12|14> <path.{ppm,png,svg}> [--part <1|2>] [--input <path|->]
+                                                   [--scale <n>]";
+
+enum Command {
+    Run(run::RunArgs),
+    Fetch(fetch::FetchArgs),
+    Submit(submit::SubmitArgs),
+    Image(image::ImageArgs),
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => Ok(Command::Run(run::parse_args(&args[1..])?)),
+        Some("fetch") => Ok(Command::Fetch(fetch::parse_args(&args[1..])?)),
+        Some("submit") => Ok(Command::Submit(submit::parse_args(&args[1..])?)),
+        Some("image") => Ok(Command::Image(image::parse_args(&args[1..])?)),
+        Some(cmd) => Err(format!("unknown command: {cmd}")),
+        None => Err("missing command".to_string()),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(msg) => {
+            eprintln!("error: {msg}");
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    };
+    match command {
+        Command::Run(args) => run::run(args),
+        Command::Fetch(args) => fetch::fetch(args),
+        Command::Submit(args) => submit::submit(args),
+        Command::Image(args) => image::image(args),
+    }
+}
@@ -0,0 +1,158 @@
+// How the runner prints its results.
+
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::answers::{self, Check};
+use crate::days::Report;
+
+#[derive(PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+// The same `part 1: ...` lines the day binaries print. When the answers are checked, the result is
+// added to the line, with a diff for wrong answers.
+pub fn print_text(report: &Report, checks: Option<&[Check]>) {
+    for (i, answer) in report.answers.iter().enumerate() {
+        let check = checks.map(|checks| &checks[i]);
+        let label = match check {
+            None => String::new(),
+            Some(Check::Correct) => " (correct)".to_string(),
+            Some(Check::Wrong { .. }) => " (wrong)".to_string(),
+            Some(Check::Unknown) => " (unknown)".to_string(),
+        };
+        // Some answers (like the CRT screen of day 10) span multiple lines, those start on a new
+        // line.
+        if answer.answer.contains('\n') {
+            println!("part {}{label}:\n{}", answer.part, answer.answer.trim_end());
+        } else {
+            println!("part {}{label}: {}", answer.part, answer.answer);
+        }
+        if let Some(Check::Wrong { expected }) = check {
+            println!("{}", answers::diff(expected, &answer.answer));
+        }
+    }
+}
+
+// One JSON object per line and part, e.g.
+//
+//     {"day":7,"part":1,"answer":"1307902","parse_time_ns":316455,"solve_time_ns":12703}
+//
+// The answer is always a string, because not all answers are numbers. Both parts are solved with
+// the same parsed input, so they have the same parse time.
+//
+// When the answers are checked, there is an additional `"check"` field (`"correct"`, `"wrong"` or
+// `"unknown"`), and `"expected"` with the known answer if it's wrong.
+pub fn print_json(day: u8, report: &Report, checks: Option<&[Check]>) {
+    for (i, answer) in report.answers.iter().enumerate() {
+        let mut object = json!({
+            "day": day,
+            "part": answer.part,
+            "answer": answer.answer,
+            "parse_time_ns": report.parse_time.as_nanos() as u64,
+            "solve_time_ns": answer.time.as_nanos() as u64,
+        });
+        match checks.map(|checks| &checks[i]) {
+            None => {}
+            Some(Check::Correct) => object["check"] = json!("correct"),
+            Some(Check::Unknown) => object["check"] = json!("unknown"),
+            Some(Check::Wrong { expected }) => {
+                object["check"] = json!("wrong");
+                object["expected"] = json!(expected);
+            }
+        }
+        println!("{object}");
+    }
+}
+
+// A failed day as JSON, e.g.
+//
+//     {"day":10,"status":"timed out","error":"no answer after 30s"}
+pub fn print_json_error(day: u8, status: &str, error: &str) {
+    println!("{}", json!({"day": day, "status": status, "error": error}));
+}
+
+// One line of the summary after running several days. A day that failed has a single row without
+// a part and times, with the error as the answer.
+pub struct Row {
+    pub day: u8,
+    pub part: Option<u8>,
+    pub status: String,
+    pub parse_time: Option<Duration>,
+    pub solve_time: Option<Duration>,
+    pub answer: String,
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    let Some(time) = time else {
+        return "-".to_string();
+    };
+    let micros = time.as_secs_f64() * 1e6;
+    if micros < 1e3 {
+        format!("{micros:.0} µs")
+    } else if micros < 1e6 {
+        format!("{:.1} ms", micros / 1e3)
+    } else {
+        format!("{:.2} s", micros / 1e6)
+    }
+}
+
+// The summary as a table, with the columns padded to the same width in every row.
+pub fn table(rows: &[Row]) -> String {
+    let mut table = format!(
+        "{:>3}  {:>4}  {:<9}  {:>9}  {:>9}  answer\n",
+        "day", "part", "status", "parse", "solve"
+    );
+    for row in rows {
+        let part = row.part.map_or("-".to_string(), |part| part.to_string());
+        // Multi-line answers (the screen of day 10) don't fit, they are printed above the table.
+        let answer = if row.answer.trim_end().contains('\n') {
+            "(see above)"
+        } else {
+            row.answer.as_str()
+        };
+        table += &format!(
+            "{:>3}  {:>4}  {:<9}  {:>9}  {:>9}  {}\n",
+            row.day,
+            part,
+            row.status,
+            format_time(row.parse_time),
+            format_time(row.solve_time),
+            answer
+        );
+    }
+    table
+}
+
+#[test]
+fn test_table() {
+    let rows = [
+        Row {
+            day: 1,
+            part: Some(2),
+            status: "correct".to_string(),
+            parse_time: Some(Duration::from_micros(120)),
+            solve_time: Some(Duration::from_micros(1500)),
+            answer: "203905".to_string(),
+        },
+        Row {
+            day: 10,
+            part: None,
+            status: "timed out".to_string(),
+            parse_time: None,
+            solve_time: None,
+            answer: "no answer after 30s".to_string(),
+        },
+    ];
+    assert_eq!(
+        table(&rows),
+        "\
+day  part  status         parse      solve  answer
+  1     2  correct       120 µs     1.5 ms  203905
+ 10     -  timed out          -          -  no answer after 30s
+"
+    );
+}
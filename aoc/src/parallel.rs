@@ -0,0 +1,173 @@
+// Solving several days at once, for `aoc run all`.
+//
+// The days are solved on a thread pool (see `pool.rs`), and every day gets its own thread on top
+// of that, so it can't take the others down with it:
+//
+// - A panic is caught with `catch_unwind` and reported as the result of that day.
+// - The worker only waits `timeout` for the answers. Rust has no way to stop a thread from the
+//   outside, so a day that takes too long (or waits for something that never happens, like the
+//   Enter key in the day 10 binary) is left running in the background and reported as timed out.
+//   It ends with the process.
+
+use std::any::Any;
+use std::panic;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::days::{Day, Report};
+use crate::pool::ThreadPool;
+
+pub enum Outcome {
+    Solved(Report),
+    ParseError(String),
+    Panicked(String),
+    TimedOut,
+}
+
+// Solves the days with `jobs` threads, and returns the outcomes in the same order as the days.
+pub fn solve_all(
+    days: &[&'static Day],
+    parts: &[u8],
+    jobs: usize,
+    timeout: Duration,
+) -> Vec<Outcome> {
+    let pool = ThreadPool::new(jobs);
+    let (sender, receiver) = mpsc::channel();
+    for (i, &day) in days.iter().enumerate() {
+        let sender = sender.clone();
+        let parts = parts.to_vec();
+        pool.execute(move || {
+            let outcome = solve_isolated(day, day.input, parts, timeout);
+            sender.send((i, outcome)).unwrap();
+        });
+    }
+    // The loop below ends once all jobs are done and have dropped their senders.
+    drop(sender);
+    let mut outcomes: Vec<Option<Outcome>> = days.iter().map(|_| None).collect();
+    for (i, outcome) in receiver {
+        outcomes[i] = Some(outcome);
+    }
+    outcomes
+        .into_iter()
+        .map(|outcome| outcome.unwrap())
+        .collect()
+}
+
+fn solve_isolated(
+    day: &'static Day,
+    input: &'static str,
+    parts: Vec<u8>,
+    timeout: Duration,
+) -> Outcome {
+    let (sender, receiver) = mpsc::channel();
+    // Named threads show up in the panic messages, like "thread 'day10' panicked at ...".
+    let spawned = thread::Builder::new()
+        .name(format!("day{}", day.number))
+        .spawn(move || {
+            let result = panic::catch_unwind(|| (day.solve)(input, &parts));
+            // After a timeout nobody is listening anymore, that's fine.
+            let _ = sender.send(result);
+        });
+    if let Err(err) = spawned {
+        return Outcome::Panicked(format!("failed to start a thread: {err}"));
+    }
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(Ok(report))) => Outcome::Solved(report),
+        Ok(Ok(Err(err))) => Outcome::ParseError(err.to_string()),
+        Ok(Err(payload)) => Outcome::Panicked(panic_message(payload.as_ref())),
+        Err(RecvTimeoutError::Timeout) => Outcome::TimedOut,
+        // The thread always sends something, unless even catching the panic failed.
+        Err(RecvTimeoutError::Disconnected) => Outcome::Panicked("no result".to_string()),
+    }
+}
+
+// `panic!("...")` with a plain string literal panics with a `&str`, with formatting arguments
+// (and in most of the standard library) with a `String`.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+use crate::days::Answer;
+
+#[cfg(test)]
+fn panics(_: &str, _: &[u8]) -> Result<Report, common::ParseError> {
+    panic!("broken on purpose")
+}
+
+#[cfg(test)]
+fn hangs(_: &str, _: &[u8]) -> Result<Report, common::ParseError> {
+    thread::sleep(Duration::from_secs(60));
+    unreachable!()
+}
+
+#[cfg(test)]
+fn parse_error(input: &str, _: &[u8]) -> Result<Report, common::ParseError> {
+    Err(common::ParseError::at(input, input, "something else"))
+}
+
+#[cfg(test)]
+fn solves(input: &str, parts: &[u8]) -> Result<Report, common::ParseError> {
+    Ok(Report {
+        parse_time: Duration::ZERO,
+        answers: parts
+            .iter()
+            .map(|&part| Answer {
+                part,
+                answer: format!("{input} {part}"),
+                time: Duration::ZERO,
+            })
+            .collect(),
+    })
+}
+
+// One broken day of every kind, and two that work.
+#[test]
+fn test_isolation() {
+    static PANICS: Day = Day {
+        number: 101,
+        input: "",
+        solve: panics,
+        stream: None,
+    };
+    static HANGS: Day = Day {
+        number: 102,
+        input: "",
+        solve: hangs,
+        stream: None,
+    };
+    static PARSE_ERROR: Day = Day {
+        number: 103,
+        input: "oops",
+        solve: parse_error,
+        stream: None,
+    };
+    static SOLVES: Day = Day {
+        number: 104,
+        input: "answer",
+        solve: solves,
+        stream: None,
+    };
+    let day1 = crate::days::get(1).unwrap();
+    let days = [&PANICS, &HANGS, &PARSE_ERROR, &SOLVES, day1];
+
+    let outcomes = solve_all(&days, &[1, 2], 2, Duration::from_millis(200));
+    assert!(matches!(&outcomes[0], Outcome::Panicked(message) if message == "broken on purpose"));
+    assert!(matches!(outcomes[1], Outcome::TimedOut));
+    assert!(matches!(&outcomes[2], Outcome::ParseError(message) if message.contains("something")));
+    match &outcomes[3] {
+        Outcome::Solved(report) => assert_eq!(report.answers[1].answer, "answer 2"),
+        _ => panic!("day 104 wasn't solved"),
+    }
+    match &outcomes[4] {
+        Outcome::Solved(report) => assert_eq!(report.answers[0].answer, "70764"),
+        _ => panic!("day 1 wasn't solved"),
+    }
+}
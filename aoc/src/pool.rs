@@ -0,0 +1,70 @@
+// A minimal thread pool, like the one from the last chapter of the Rust book: a fixed number of
+// worker threads take jobs from a shared channel until the channel is closed.
+
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+// A job is any closure we can send to another thread and call once.
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+pub struct ThreadPool {
+    workers: Vec<thread::JoinHandle<()>>,
+    // An `Option`, so `drop` can take the sender out and close the channel.
+    sender: Option<mpsc::Sender<Job>>,
+}
+
+impl ThreadPool {
+    pub fn new(size: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        // All workers share one receiver, so it goes into an `Arc<Mutex<...>>`.
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..size.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || loop {
+                    // The lock guard is a temporary that is dropped at the end of this
+                    // statement, so other workers can take jobs while we run this one.
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        // The pool was dropped and there are no jobs left.
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+        ThreadPool {
+            workers,
+            sender: Some(sender),
+        }
+    }
+
+    pub fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        self.sender.as_ref().unwrap().send(Box::new(job)).unwrap();
+    }
+}
+
+impl Drop for ThreadPool {
+    // Waits until all jobs are done.
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            // A worker only fails if one of its jobs panicked, and there is nothing left to do
+            // for it then.
+            let _ = worker.join();
+        }
+    }
+}
+
+#[test]
+fn test_pool() {
+    let (sender, receiver) = mpsc::channel();
+    let pool = ThreadPool::new(3);
+    for i in 0..10 {
+        let sender = sender.clone();
+        pool.execute(move || sender.send(i * i).unwrap());
+    }
+    drop(pool);
+    drop(sender);
+    assert_eq!(receiver.iter().sum::<i32>(), 285);
+}
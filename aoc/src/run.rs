@@ -0,0 +1,261 @@
+// `aoc run`: solves one or all days, and prints (and optionally checks) the answers.
+//
+// A single day is solved right here, so a panic shows up as usual. Several days are solved in
+// parallel, each on its own thread with a timeout (see `parallel.rs`), so a broken day only fails
+// itself. After the answers there's a summary table with the status and timings of every part.
+//
+// With `--stream`, a single day reads its input bit by bit instead of all at once (see
+// `common::stream`), for inputs that are too large for memory.
+
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use crate::answers::{self, Answers, Check};
+use crate::days::{self, Day};
+use crate::output::{self, Format, Row};
+use crate::parallel::{self, Outcome};
+
+pub struct RunArgs {
+    days: Vec<&'static Day>,
+    parts: Vec<u8>,
+    input: Option<String>,
+    format: Format,
+    check: bool,
+    save: bool,
+    answers: String,
+    jobs: usize,
+    timeout: Duration,
+    stream: bool,
+}
+
+pub fn parse_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut args = args.iter();
+    let days = match args.next().map(|s| s.as_str()) {
+        Some("all") => days::DAYS.iter().collect(),
+        Some(day) => {
+            let number = day.parse().map_err(|_| format!("invalid day: {day}"))?;
+            vec![days::get(number).ok_or(format!("no solution for day {number}"))?]
+        }
+        None => return Err("missing day".to_string()),
+    };
+    let mut parts = vec![1, 2];
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut check = false;
+    let mut save = false;
+    let mut answers = answers::DEFAULT_PATH.to_string();
+    // As many threads as there are cores, if we can find out how many there are.
+    let mut jobs = thread::available_parallelism().map_or(4, |n| n.get());
+    let mut timeout = Duration::from_secs(30);
+    let mut stream = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                parts = match value.as_str() {
+                    "1" => vec![1],
+                    "2" => vec![2],
+                    _ => return Err(format!("invalid part: {value}")),
+                };
+            }
+            "--input" => {
+                let value = args.next().ok_or("--input needs a value")?;
+                input = Some(value.clone());
+            }
+            "--format" => {
+                let value = args.next().ok_or("--format needs a value")?;
+                format = match value.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => return Err(format!("invalid format: {value}")),
+                };
+            }
+            "--check" => check = true,
+            "--save" => save = true,
+            "--answers" => {
+                let value = args.next().ok_or("--answers needs a value")?;
+                answers = value.clone();
+            }
+            "--jobs" => {
+                let value = args.next().ok_or("--jobs needs a value")?;
+                jobs = match value.parse() {
+                    Ok(jobs) if jobs > 0 => jobs,
+                    _ => return Err(format!("invalid number of jobs: {value}")),
+                };
+            }
+            "--timeout" => {
+                let value = args.next().ok_or("--timeout needs a value")?;
+                let seconds: f64 = value
+                    .parse()
+                    .ok()
+                    .filter(|seconds| *seconds > 0.0)
+                    .ok_or(format!("invalid timeout: {value}"))?;
+                timeout = Duration::from_secs_f64(seconds);
+            }
+            "--stream" => stream = true,
+            arg => return Err(format!("unknown argument: {arg}")),
+        }
+    }
+    if input.is_some() && days.len() > 1 {
+        return Err("--input can only be used with a single day".to_string());
+    }
+    if stream {
+        match days[..] {
+            [day] if day.stream.is_none() => {
+                return Err(format!(
+                    "day {} can't read its input bit by bit",
+                    day.number
+                ))
+            }
+            [_] => {}
+            _ => return Err("--stream can only be used with a single day".to_string()),
+        }
+    }
+    Ok(RunArgs {
+        days,
+        parts,
+        input,
+        format,
+        check,
+        save,
+        answers,
+        jobs,
+        timeout,
+        stream,
+    })
+}
+
+pub fn run(args: RunArgs) {
+    let mut answers = match Answers::load(Path::new(&args.answers)) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("error: failed to read {}: {err}", args.answers);
+            process::exit(1);
+        }
+    };
+    let outcomes = if args.stream {
+        let day = args.days[0];
+        let mut reader =
+            common::input::reader(args.input.as_deref(), day.input).unwrap_or_else(|err| {
+                eprintln!(
+                    "error: failed to read {}: {err}",
+                    args.input.unwrap_or_default()
+                );
+                process::exit(1);
+            });
+        match (day.stream.unwrap())(&mut reader, &args.parts) {
+            Ok(report) => vec![Outcome::Solved(report)],
+            Err(err) => {
+                eprintln!("error: day {}: {err}", day.number);
+                process::exit(1);
+            }
+        }
+    } else if let [day] = args.days[..] {
+        let input = common::input::load_or_exit(args.input.as_deref(), day.input);
+        match (day.solve)(&input, &args.parts) {
+            Ok(report) => vec![Outcome::Solved(report)],
+            Err(err) => {
+                eprintln!("error: failed to parse input for day {}: {err}", day.number);
+                process::exit(1);
+            }
+        }
+    } else {
+        parallel::solve_all(&args.days, &args.parts, args.jobs, args.timeout)
+    };
+    let mut wrong = 0;
+    let mut failed = 0;
+    let mut rows = vec![];
+
+    for (i, (day, outcome)) in args.days.iter().zip(&outcomes).enumerate() {
+        if args.format == Format::Text && args.days.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("== day {} ==", day.number);
+        }
+        let report = match outcome {
+            Outcome::Solved(report) => report,
+            failure => {
+                let (status, error) = match failure {
+                    Outcome::ParseError(err) => ("parse error", err.clone()),
+                    Outcome::Panicked(message) => ("panicked", message.clone()),
+                    _ => ("timed out", format!("no answer after {:?}", args.timeout)),
+                };
+                match args.format {
+                    Format::Text => println!("error: {error}"),
+                    Format::Json => output::print_json_error(day.number, status, &error),
+                }
+                failed += 1;
+                rows.push(Row {
+                    day: day.number,
+                    part: None,
+                    status: status.to_string(),
+                    parse_time: None,
+                    solve_time: None,
+                    // Only the first line, the whole message is printed above.
+                    answer: error.lines().next().unwrap_or_default().to_string(),
+                });
+                continue;
+            }
+        };
+        let checks: Option<Vec<Check>> = args.check.then(|| {
+            report
+                .answers
+                .iter()
+                .map(|a| answers.check(day.number, a.part, &a.answer))
+                .collect()
+        });
+        if let Some(checks) = &checks {
+            wrong += checks
+                .iter()
+                .filter(|c| matches!(c, Check::Wrong { .. }))
+                .count();
+        }
+        match args.format {
+            Format::Text => output::print_text(report, checks.as_deref()),
+            Format::Json => output::print_json(day.number, report, checks.as_deref()),
+        }
+        for (i, answer) in report.answers.iter().enumerate() {
+            let status = match checks.as_ref().map(|checks| &checks[i]) {
+                None => "ok",
+                Some(Check::Correct) => "correct",
+                Some(Check::Wrong { .. }) => "wrong",
+                Some(Check::Unknown) => "unknown",
+            };
+            rows.push(Row {
+                day: day.number,
+                part: Some(answer.part),
+                status: status.to_string(),
+                parse_time: Some(report.parse_time),
+                solve_time: Some(answer.time),
+                answer: answer.answer.clone(),
+            });
+        }
+        if args.save {
+            for answer in &report.answers {
+                answers.set(day.number, answer.part, &answer.answer);
+            }
+        }
+    }
+
+    if args.format == Format::Text && args.days.len() > 1 {
+        print!("\n{}", output::table(&rows));
+    }
+    if args.save {
+        if let Err(err) = answers.save() {
+            eprintln!("error: failed to write {}: {err}", args.answers);
+            process::exit(1);
+        }
+    }
+    if failed > 0 {
+        eprintln!("error: {failed} day(s) failed");
+    }
+    if wrong > 0 {
+        eprintln!("error: {wrong} answer(s) don't match {}", args.answers);
+    }
+    if failed > 0 || wrong > 0 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,197 @@
+// Remembers what happened to the answers we submitted, so we never send an answer that we already
+// know is wrong. It's stored next to the cached inputs (`.aoc/<year>/submissions.json`):
+//
+//     {
+//       "7": {
+//         "1": {
+//           "correct": null,
+//           "wrong": ["1000", "1400000"],
+//           "too_high": 1400000,
+//           "too_low": 1000
+//         }
+//       }
+//     }
+//
+// For numeric answers the website sometimes tells us that the answer was too high or too low. We
+// keep the tightest bounds, then every number outside of them is known to be wrong as well.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub correct: Option<String>,
+    #[serde(default)]
+    pub wrong: Vec<String>,
+    // The smallest answer that was too high and the largest answer that was too low.
+    pub too_high: Option<i64>,
+    pub too_low: Option<i64>,
+}
+
+// What the website told us about an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+// Why we don't submit an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { correct: String },
+    KnownWrong,
+    TooHigh { bound: i64 },
+    TooLow { bound: i64 },
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadySolved { correct } => {
+                write!(f, "already solved, the correct answer is {correct}")
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already submitted and is wrong"),
+            Refusal::TooHigh { bound } => {
+                write!(f, "too high, {bound} was already too high")
+            }
+            Refusal::TooLow { bound } => write!(f, "too low, {bound} was already too low"),
+        }
+    }
+}
+
+pub struct Submissions {
+    path: PathBuf,
+    days: BTreeMap<u8, BTreeMap<u8, History>>,
+}
+
+impl Submissions {
+    // Like `Answers`, a missing file is the same as an empty one.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let days = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(Submissions {
+            path: path.to_path_buf(),
+            days,
+        })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string_pretty(&self.days)?;
+        fs::write(&self.path, content + "\n")
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&History> {
+        self.days.get(&day)?.get(&part)
+    }
+
+    // Checks if we already know that submitting `answer` is pointless.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Option<Refusal> {
+        let history = self.get(day, part)?;
+        if let Some(correct) = &history.correct {
+            return Some(Refusal::AlreadySolved {
+                correct: correct.clone(),
+            });
+        }
+        if history.wrong.iter().any(|wrong| wrong == answer) {
+            return Some(Refusal::KnownWrong);
+        }
+        let number: i64 = answer.parse().ok()?;
+        match (history.too_high, history.too_low) {
+            (Some(bound), _) if number >= bound => Some(Refusal::TooHigh { bound }),
+            (_, Some(bound)) if number <= bound => Some(Refusal::TooLow { bound }),
+            _ => None,
+        }
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, answer: &str, outcome: &Outcome) {
+        let history = self.days.entry(day).or_default().entry(part).or_default();
+        if *outcome == Outcome::Right {
+            history.correct = Some(answer.to_string());
+            return;
+        }
+        if !history.wrong.iter().any(|wrong| wrong == answer) {
+            history.wrong.push(answer.to_string());
+        }
+        let number = answer.parse::<i64>().ok();
+        match (outcome, number) {
+            (Outcome::TooHigh, Some(number)) => {
+                history.too_high = Some(history.too_high.map_or(number, |b| b.min(number)));
+            }
+            (Outcome::TooLow, Some(number)) => {
+                history.too_low = Some(history.too_low.map_or(number, |b| b.max(number)));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn test_record_and_check() {
+    let mut submissions = Submissions {
+        path: PathBuf::new(),
+        days: BTreeMap::new(),
+    };
+    assert_eq!(submissions.check(7, 1, "1000"), None);
+
+    submissions.record(7, 1, "1000", &Outcome::TooLow);
+    submissions.record(7, 1, "900", &Outcome::TooLow);
+    submissions.record(7, 1, "5000", &Outcome::TooHigh);
+    submissions.record(7, 1, "2000", &Outcome::Wrong);
+    let history = submissions.get(7, 1).unwrap();
+    assert_eq!(history.too_low, Some(1000));
+    assert_eq!(history.too_high, Some(5000));
+    assert_eq!(history.wrong, ["1000", "900", "5000", "2000"]);
+
+    assert_eq!(submissions.check(7, 1, "2000"), Some(Refusal::KnownWrong));
+    assert_eq!(submissions.check(7, 1, "1000"), Some(Refusal::KnownWrong));
+    assert_eq!(
+        submissions.check(7, 1, "999"),
+        Some(Refusal::TooLow { bound: 1000 })
+    );
+    assert_eq!(
+        submissions.check(7, 1, "6000"),
+        Some(Refusal::TooHigh { bound: 5000 })
+    );
+    assert_eq!(submissions.check(7, 1, "3000"), None);
+    // Bounds don't say anything about answers that aren't numbers.
+    assert_eq!(submissions.check(7, 1, "ABC"), None);
+    // And other parts are not affected.
+    assert_eq!(submissions.check(7, 2, "6000"), None);
+
+    submissions.record(7, 1, "3000", &Outcome::Right);
+    assert_eq!(
+        submissions.check(7, 1, "3000"),
+        Some(Refusal::AlreadySolved {
+            correct: "3000".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_load_and_save() {
+    let dir = std::env::temp_dir().join(format!("aoc-test-submissions-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("2022/submissions.json");
+    let mut submissions = Submissions::load(&path).unwrap();
+    submissions.record(1, 2, "42", &Outcome::TooHigh);
+    submissions.save().unwrap();
+
+    let submissions = Submissions::load(&path).unwrap();
+    assert_eq!(
+        submissions.check(1, 2, "43"),
+        Some(Refusal::TooHigh { bound: 42 })
+    );
+}
@@ -0,0 +1,319 @@
+// `aoc submit`: solves a day and sends the answer to the website.
+//
+// The website answers with a whole HTML page, we only look for the sentences that tell us if the
+// answer was right (see `parse_response`). Every outcome is recorded in the submissions file (see
+// `submissions.rs`), and answers that are known to be wrong are never sent again.
+
+use std::fmt;
+use std::process;
+
+use crate::client::{self, Client, Config};
+use crate::days;
+use crate::fetch::parse_day;
+use crate::submissions::{Outcome, Refusal, Submissions};
+
+pub struct SubmitArgs {
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+    input: Option<String>,
+    config: Config,
+}
+
+// Everything the website can tell us when we submit an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Outcome(Outcome),
+    // We submitted too quickly after the last answer. Contains the time left, like "34s".
+    Wait(Option<String>),
+    // The part is already solved, or part 2 isn't unlocked yet.
+    WrongLevel,
+    // None of the above, contains the text of the response.
+    Unknown(String),
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Refused(Refusal),
+    Client(client::Error),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Refused(refusal) => write!(f, "not submitting: {refusal}"),
+            Error::Client(err) => write!(f, "{err}"),
+            Error::Io(err) => write!(f, "failed to update submissions: {err}"),
+        }
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<SubmitArgs, String> {
+    let mut args = args.iter();
+    let day = parse_day(args.next())?;
+    let part = match args.next().map(|s| s.as_str()) {
+        Some("1") => 1,
+        Some("2") => 2,
+        Some(part) => return Err(format!("invalid part: {part}")),
+        None => return Err("missing part".to_string()),
+    };
+    let mut answer = None;
+    let mut input = None;
+    let mut config = Config::from_env();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answer" => answer = Some(args.next().ok_or("--answer needs a value")?.clone()),
+            "--input" => input = Some(args.next().ok_or("--input needs a value")?.clone()),
+            arg => {
+                if !config.parse_option(arg, &mut args)? {
+                    return Err(format!("unknown argument: {arg}"));
+                }
+            }
+        }
+    }
+    if answer.is_none() && days::get(day).is_none() {
+        return Err(format!("no solution for day {day}, use --answer"));
+    }
+    Ok(SubmitArgs {
+        day,
+        part,
+        answer,
+        input,
+        config,
+    })
+}
+
+// Looks for the interesting sentences in the response page.
+pub fn parse_response(html: &str) -> Response {
+    if html.contains("That's the right answer") {
+        Response::Outcome(Outcome::Right)
+    } else if html.contains("your answer is too high") {
+        Response::Outcome(Outcome::TooHigh)
+    } else if html.contains("your answer is too low") {
+        Response::Outcome(Outcome::TooLow)
+    } else if html.contains("That's not the right answer") {
+        Response::Outcome(Outcome::Wrong)
+    } else if html.contains("You gave an answer too recently") {
+        // "... You have 34s left to wait."
+        let left = html.split_once("You have ").and_then(|(_, rest)| {
+            rest.split_once(" left to wait")
+                .map(|(left, _)| left.to_string())
+        });
+        Response::Wait(left)
+    } else if html.contains("You don't seem to be solving the right level") {
+        Response::WrongLevel
+    } else {
+        Response::Unknown(article_text(html))
+    }
+}
+
+// The interesting part of a page is in its `<article>`, without the tags it's readable enough.
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article>"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+// Submits the answer, unless the submissions already tell us that it's wrong, and records what the
+// website says about it.
+pub fn submit_answer(
+    client: &Client,
+    submissions: &mut Submissions,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Response, Error> {
+    if let Some(refusal) = submissions.check(day, part, answer) {
+        return Err(Error::Refused(refusal));
+    }
+    let path = format!("/{}/day/{day}/answer", client.year());
+    let level = part.to_string();
+    let html = client
+        .post(&path, &[("level", &level), ("answer", answer)])
+        .map_err(Error::Client)?;
+    let response = parse_response(&html);
+    if let Response::Outcome(outcome) = &response {
+        submissions.record(day, part, answer, outcome);
+        submissions.save().map_err(Error::Io)?;
+    }
+    Ok(response)
+}
+
+fn solve(args: &SubmitArgs) -> String {
+    let day = days::get(args.day).unwrap();
+    let input = common::input::load_or_exit(args.input.as_deref(), day.input);
+    match (day.solve)(&input, &[args.part]) {
+        Ok(mut report) => report.answers.remove(0).answer,
+        Err(err) => {
+            eprintln!("error: failed to parse input for day {}: {err}", args.day);
+            process::exit(1);
+        }
+    }
+}
+
+pub fn submit(args: SubmitArgs) {
+    let answer = match &args.answer {
+        Some(answer) => answer.clone(),
+        None => solve(&args),
+    };
+    // Day 10 part 2 draws letters on a screen, we can't read those (yet).
+    if answer.contains('\n') {
+        eprintln!("error: the answer has more than one line, use --answer:\n{answer}");
+        process::exit(1);
+    }
+    let client = Client::new(args.config.clone());
+    let path = client.year_dir().join("submissions.json");
+    let mut submissions = match Submissions::load(&path) {
+        Ok(submissions) => submissions,
+        Err(err) => {
+            eprintln!("error: failed to read {}: {err}", path.display());
+            process::exit(1);
+        }
+    };
+
+    println!("day {} part {}: submitting {answer}", args.day, args.part);
+    match submit_answer(&client, &mut submissions, args.day, args.part, &answer) {
+        Ok(Response::Outcome(Outcome::Right)) => println!("that's the right answer!"),
+        Ok(response) => {
+            match response {
+                Response::Outcome(Outcome::TooHigh) => println!("wrong, the answer is too high"),
+                Response::Outcome(Outcome::TooLow) => println!("wrong, the answer is too low"),
+                Response::Outcome(_) => println!("wrong"),
+                Response::Wait(Some(left)) => println!("submitted too recently, wait {left}"),
+                Response::Wait(None) => println!("submitted too recently, wait a bit"),
+                Response::WrongLevel => {
+                    println!("wrong level, is the part already solved or not unlocked yet?")
+                }
+                Response::Unknown(text) => println!("unexpected response:\n{text}"),
+            }
+            process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+fn test_setup(
+    name: &str,
+    status: u16,
+    body: &str,
+) -> (crate::mock::MockServer, Client, Submissions) {
+    let server = crate::mock::MockServer::start(status, body);
+    let client = Client::new(client::test_config(name, &server.url));
+    let submissions = Submissions::load(&client.year_dir().join("submissions.json")).unwrap();
+    (server, client, submissions)
+}
+
+#[test]
+fn test_parse_response() {
+    let page = |text: &str| format!("<html><main><article><p>{text}</p></article></main></html>");
+    assert_eq!(
+        parse_response(&page(
+            "That's the right answer!  You are one gold star closer."
+        )),
+        Response::Outcome(Outcome::Right)
+    );
+    assert_eq!(
+        parse_response(&page("That's not the right answer.  If you're stuck, ...")),
+        Response::Outcome(Outcome::Wrong)
+    );
+    assert_eq!(
+        parse_response(&page(
+            "That's not the right answer; your answer is too high."
+        )),
+        Response::Outcome(Outcome::TooHigh)
+    );
+    assert_eq!(
+        parse_response(&page(
+            "That's not the right answer; your answer is too low."
+        )),
+        Response::Outcome(Outcome::TooLow)
+    );
+    assert_eq!(
+        parse_response(&page(
+            "You gave an answer too recently; you have to wait after submitting an answer before \
+             trying again.  You have 34s left to wait. <a href=\"/2022/day/7\">[Return to Day 7]</a>"
+        )),
+        Response::Wait(Some("34s".to_string()))
+    );
+    assert_eq!(
+        parse_response(&page(
+            "You don't seem to be solving the right level.  Did you already complete it?"
+        )),
+        Response::WrongLevel
+    );
+    assert_eq!(
+        parse_response(&page("Something <em>else</em>.")),
+        Response::Unknown("Something else.".to_string())
+    );
+}
+
+#[test]
+fn test_submit_too_high() {
+    let (server, client, mut submissions) = test_setup(
+        "submit-too-high",
+        200,
+        "<article><p>That's not the right answer; your answer is too high.</p></article>",
+    );
+    let response = submit_answer(&client, &mut submissions, 7, 1, "5000").unwrap();
+    assert_eq!(response, Response::Outcome(Outcome::TooHigh));
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("POST /2022/day/7/answer HTTP/1.1\r\n"));
+    assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=5000"));
+
+    // The same answer or a higher one are not sent again, also after loading the file again.
+    let mut submissions = Submissions::load(&client.year_dir().join("submissions.json")).unwrap();
+    for answer in ["5000", "6000"] {
+        let result = submit_answer(&client, &mut submissions, 7, 1, answer);
+        assert!(matches!(result, Err(Error::Refused(_))));
+    }
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_submit_right() {
+    let (_server, client, mut submissions) = test_setup(
+        "submit-right",
+        200,
+        "<article><p>That's the right answer!</p></article>",
+    );
+    let response = submit_answer(&client, &mut submissions, 7, 2, "ABC").unwrap();
+    assert_eq!(response, Response::Outcome(Outcome::Right));
+    assert_eq!(
+        submissions.get(7, 2).unwrap().correct.as_deref(),
+        Some("ABC")
+    );
+}
+
+#[test]
+fn test_submit_wait() {
+    // Having to wait says nothing about the answer, so it's not recorded.
+    let (server, client, mut submissions) = test_setup(
+        "submit-wait",
+        200,
+        "<article><p>You gave an answer too recently. You have 1m 2s left to wait.</p></article>",
+    );
+    for _ in 0..2 {
+        let response = submit_answer(&client, &mut submissions, 1, 1, "42").unwrap();
+        assert_eq!(response, Response::Wait(Some("1m 2s".to_string())));
+    }
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(submissions.get(1, 1), None);
+}
@@ -0,0 +1,120 @@
+// The error returned by all the input parsers. It points at the part of the input that couldn't be
+// parsed, and says what was expected there instead.
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // Line and column are 1-based, like in an editor.
+    pub line: usize,
+    pub column: usize,
+    // The whole line the error is on...
+    pub text: String,
+    // ... which starts at this column, 1 unless only the end of a long line was kept (see
+    // `from_column()`).
+    pub text_column: usize,
+    // The part of the line that couldn't be parsed (may be empty, e.g. at the end of a line).
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    // Creates an error for `part`, which has to be a slice of `input`. The parsers only ever
+    // split the input up into smaller slices (with `lines()`, `split_once()` etc.), so we can find
+    // out where `part` is by comparing the addresses of the two slices, no need to keep track of
+    // line numbers and positions everywhere.
+    pub fn at(input: &str, part: &str, expected: impl Into<String>) -> Self {
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + part.len() <= input.len())
+            .unwrap_or(0);
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let text = input[line_start..].lines().next().unwrap_or_default();
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: text.to_string(),
+            text_column: 1,
+            found: part.lines().next().unwrap_or_default().to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    // For parsers that read the input line by line (see `stream.rs`) and call `at()` with only the
+    // current line as the input, which makes it line 1. This moves the error to the real line.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    // For parsers that don't keep all of a very long line (like day 6 reading its signal as a
+    // stream) and call `at()` with only the last part of it, which starts at column `start` of the
+    // real line. This moves the error to the real column.
+    pub fn from_column(mut self, start: usize) -> Self {
+        self.column += start - 1;
+        self.text_column = start;
+        self
+    }
+}
+
+// Prints the error along with the line it's on, and marks the offending part:
+//
+//     line 4, column 6: expected a number, found "x"
+//       |
+//     4 | move x from 1 to 2
+//       |      ^
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: expected {}", self.line, self.column, self.expected)?;
+        if !self.found.is_empty() {
+            write!(f, ", found {:?}", self.found)?;
+        }
+        writeln!(f)?;
+        let margin = " ".repeat(self.line.to_string().len());
+        // If we only have the end of the line, the `…` stands for the rest.
+        let cut = if self.text_column > 1 { "…" } else { "" };
+        let indent = " ".repeat(cut.chars().count() + self.column - self.text_column);
+        let marker = "^".repeat(self.found.chars().count().max(1));
+        writeln!(f, "{margin} |")?;
+        writeln!(f, "{} | {cut}{}", self.line, self.text)?;
+        write!(f, "{margin} | {indent}{marker}")
+    }
+}
+
+impl Error for ParseError {}
+
+#[test]
+fn test_parse_error_position() {
+    let input = "move 1 from 2 to 3\nmove x from 1 to 2\n";
+    let line = input.lines().nth(1).unwrap();
+    let err = ParseError::at(input, &line[5..6], "a number");
+    assert_eq!((err.line, err.column), (2, 6));
+    assert_eq!(err.text, "move x from 1 to 2");
+    assert_eq!(err.found, "x");
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 6: expected a number, found \"x\"\n  |\n2 | move x from 1 to 2\n  |      ^"
+    );
+}
+
+#[test]
+fn test_parse_error_outside_input() {
+    // Not a slice of the input, so we can only point at the start.
+    let err = ParseError::at("abc\ndef", "xyz", "something else");
+    assert_eq!((err.line, err.column), (1, 1));
+    assert_eq!(err.text, "abc");
+}
+
+#[test]
+fn test_parse_error_from_column() {
+    // The last part of a line whose first 1000 characters weren't kept.
+    let end = "xyz1";
+    let err = ParseError::at(end, &end[3..], "a letter").from_column(1001);
+    assert_eq!((err.line, err.column, err.text.as_str()), (1, 1004, "xyz1"));
+    assert_eq!(
+        err.to_string(),
+        "line 1, column 1004: expected a letter, found \"1\"\n  |\n1 | …xyz1\n  |     ^"
+    );
+}
@@ -0,0 +1,99 @@
+// Every day bundles its own `input.txt` with `include_str!`, but it's useful to be able to run the
+// same binary against the examples from the puzzle text or other inputs without recompiling.
+
+use std::borrow::Cow;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::process;
+
+// Reads the input from `path`, or from stdin if the path is `-`. Without a path, the bundled
+// input is used.
+// `Cow` ("clone on write") lets us return either the borrowed `&'static str` or a `String` we
+// read at runtime, without copying the bundled input.
+pub fn load(path: Option<&str>, bundled: &'static str) -> io::Result<Cow<'static, str>> {
+    match path {
+        None => Ok(Cow::Borrowed(bundled)),
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(Cow::Owned(input))
+        }
+        Some(path) => Ok(Cow::Owned(fs::read_to_string(path)?)),
+    }
+}
+
+// Like `load`, but takes the path from the first command line argument and exits with an error
+// message if it can't be read. Used by the day binaries.
+pub fn from_args(bundled: &'static str) -> Cow<'static, str> {
+    let path = env::args().nth(1);
+    load_or_exit(path.as_deref(), bundled)
+}
+
+pub fn load_or_exit(path: Option<&str>, bundled: &'static str) -> Cow<'static, str> {
+    match load(path, bundled) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: failed to read {}: {err}", path.unwrap_or_default());
+            process::exit(1);
+        }
+    }
+}
+
+// Like `load`, but for reading the input bit by bit (see `stream.rs`) instead of all at once.
+pub fn reader(path: Option<&str>, bundled: &'static str) -> io::Result<Box<dyn BufRead>> {
+    match path {
+        None => Ok(Box::new(bundled.as_bytes())),
+        Some("-") => Ok(Box::new(io::stdin().lock())),
+        Some(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
+}
+
+// Makes inputs look the same, however they were saved:
+//
+// - A byte order mark at the start (some Windows editors add one) is removed.
+// - Windows line endings (`\r\n`) become `\n`.
+// - Trailing whitespace and blank lines at the end are removed, and the input ends with exactly
+//   one `\n` (unless it's empty). Whitespace at the start or in the middle of lines is kept, the
+//   crane drawing of day 5 needs it.
+//
+// Most inputs are already normalized, those are returned as they are without copying them.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let content = input.trim_end();
+    if !content.contains('\r') {
+        if content.is_empty() && input.is_empty() {
+            return Cow::Borrowed(input);
+        }
+        if !content.is_empty() && input.len() == content.len() + 1 && input.ends_with('\n') {
+            return Cow::Borrowed(input);
+        }
+    }
+    let mut normalized = content.replace("\r\n", "\n");
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    Cow::Owned(normalized)
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(normalize("1\n2\n"), "1\n2\n");
+    assert!(matches!(normalize("1\n2\n"), Cow::Borrowed(_)));
+    assert_eq!(normalize("1\r\n2\r\n"), "1\n2\n");
+    assert_eq!(normalize("\u{feff}1\n2"), "1\n2\n");
+    assert_eq!(normalize("1\n\n2\n\n\n  \n"), "1\n\n2\n");
+    assert_eq!(normalize("  [D]\r\n1 \r\n"), "  [D]\n1\n");
+    assert_eq!(normalize(""), "");
+    assert_eq!(normalize("\r\n\r\n"), "");
+}
+
+#[test]
+fn test_load() {
+    assert_eq!(load(None, "bundled").unwrap(), "bundled");
+    let path = env::temp_dir().join("common_test_load.txt");
+    fs::write(&path, "from file").unwrap();
+    assert_eq!(load(path.to_str(), "bundled").unwrap(), "from file");
+    fs::remove_file(&path).unwrap();
+    assert!(load(Some("does/not/exist.txt"), "bundled").is_err());
+}
@@ -0,0 +1,10 @@
+// Code shared between all days and the `aoc` runner.
+
+pub mod error;
+pub mod input;
+pub mod solution;
+pub mod stream;
+
+pub use error::ParseError;
+pub use solution::{parse_or_exit, run, test_crlf, Solution};
+pub use stream::{LineReader, StreamError, StreamSolution};
@@ -0,0 +1,62 @@
+// All days implement this trait, so the runner (and tests, benchmarks, ...) can treat them the
+// same way: parse the input once, then solve both parts using the parsed input.
+
+use std::fmt::Display;
+use std::process;
+
+use crate::ParseError;
+
+pub trait Solution {
+    // The parsed puzzle input.
+    type Input;
+    // The answers can be anything that can be printed. Most days return numbers, but e.g. day 5
+    // returns a string and day 10 the CRT screen.
+    type Answer1: Display;
+    type Answer2: Display;
+
+    // The parser of the day. It only ever sees normalized input: `\n` line endings, no byte order
+    // mark, and exactly one newline at the end (see `input::normalize`).
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    // What everyone else calls: normalizes the input, then parses it. Inputs saved on Windows or
+    // with an editor that adds blank lines at the end work the same as the original ones.
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_normalized(&crate::input::normalize(input))
+    }
+}
+
+// What the day binaries do: read the input (see `input::from_args`), parse it and print both
+// answers.
+pub fn run<S: Solution>(bundled: &'static str) {
+    let input = crate::input::from_args(bundled);
+    let input = parse_or_exit::<S>(&input);
+
+    let answer = S::part1(&input);
+    println!("part 1: {answer}");
+
+    let answer = S::part2(&input);
+    println!("part 2: {answer}");
+}
+
+// Parses the input, or prints the parse error and exits.
+pub fn parse_or_exit<S: Solution>(input: &str) -> S::Input {
+    match S::parse(input) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: failed to parse input: {err}");
+            process::exit(1);
+        }
+    }
+}
+
+// A test for every day: the same input saved on Windows (with `\r\n` line endings and a byte order
+// mark), and with blank lines at the end, gives the same answers. They're compared the way they're
+// printed, because not all of them implement `PartialEq` (like the CRT screen of day 10).
+pub fn test_crlf<S: Solution>(input: &str) {
+    let crlf = format!("\u{feff}{}\r\n\r\n", input.replace('\n', "\r\n"));
+    let (parsed, expected) = (S::parse(&crlf).unwrap(), S::parse(input).unwrap());
+    assert_eq!(S::part1(&parsed).to_string(), S::part1(&expected).to_string());
+    assert_eq!(S::part2(&parsed).to_string(), S::part2(&expected).to_string());
+}
@@ -0,0 +1,134 @@
+// Solving a day while reading the input, for inputs too large to keep in memory (like generated
+// inputs of a few gigabytes for stress tests).
+//
+// `Solution::parse` needs the whole input as a `&str`. Days that only ever look at one line (or a
+// few lines) at a time can implement `StreamSolution` as well, and compute both answers in a
+// single pass over a `BufRead`, keeping only what they need for the answers.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::{ParseError, Solution};
+
+pub trait StreamSolution: Solution {
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(Self::Answer1, Self::Answer2), StreamError>;
+}
+
+// Reading the input can fail as well as parsing it.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(err) => write!(f, "failed to read input: {err}"),
+            StreamError::Parse(err) => write!(f, "failed to parse input: {err}"),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+// These let us use `?` on both kinds of errors in `solve_stream`.
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        StreamError::Io(err)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(err: ParseError) -> Self {
+        StreamError::Parse(err)
+    }
+}
+
+// Reads the input line by line into the same buffer, so only one line is in memory at a time.
+// The lines look like the ones of a normalized input (see `input::normalize`): without the line
+// ending (`\n` or `\r\n`) or the byte order mark, and the blank lines at the end of the input are
+// left out. Unlike there, trailing whitespace is removed from every line, not only the last one.
+//
+// This can't be an `Iterator`, because the lines it returns borrow the buffer, which is
+// overwritten by the next line.
+pub struct LineReader<R> {
+    reader: R,
+    buffer: String,
+    number: usize,
+    // Blank lines we haven't returned yet: we only know whether they're at the end of the input
+    // once we've read the next line that isn't blank.
+    blank: usize,
+    // Whether `buffer` holds the line after the blank ones.
+    waiting: bool,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> Self {
+        LineReader {
+            reader,
+            buffer: String::new(),
+            number: 0,
+            blank: 0,
+            waiting: false,
+        }
+    }
+
+    // Returns the next line with its (1-based) number, or `None` at the end of the input.
+    pub fn next_line(&mut self) -> io::Result<Option<(usize, &str)>> {
+        if self.blank > 0 {
+            self.blank -= 1;
+            self.number += 1;
+            return Ok(Some((self.number, "")));
+        }
+        if self.waiting {
+            self.waiting = false;
+            self.number += 1;
+            return Ok(Some((self.number, self.line())));
+        }
+        loop {
+            self.buffer.clear();
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                return Ok(None);
+            }
+            if self.number + self.blank == 0 && self.buffer.starts_with('\u{feff}') {
+                self.buffer.drain(..'\u{feff}'.len_utf8());
+            }
+            if !self.line().is_empty() {
+                break;
+            }
+            self.blank += 1;
+        }
+        if self.blank > 0 {
+            self.waiting = true;
+            self.blank -= 1;
+            self.number += 1;
+            return Ok(Some((self.number, "")));
+        }
+        self.number += 1;
+        Ok(Some((self.number, self.line())))
+    }
+
+    fn line(&self) -> &str {
+        self.buffer.trim_end()
+    }
+}
+
+#[test]
+fn test_line_reader() {
+    let input = "\u{feff}1\r\n2  \n\n\n3\n\n \r\n";
+    let mut reader = LineReader::new(input.as_bytes());
+    let mut lines = vec![];
+    while let Some((number, line)) = reader.next_line().unwrap() {
+        lines.push((number, line.to_string()));
+    }
+    let expected = [(1, "1"), (2, "2"), (3, ""), (4, ""), (5, "3")];
+    assert_eq!(lines, expected.map(|(number, line)| (number, line.to_string())));
+}
+
+#[test]
+fn test_line_reader_empty() {
+    let mut reader = LineReader::new("\n\n".as_bytes());
+    assert!(reader.next_line().unwrap().is_none());
+}
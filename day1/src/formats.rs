@@ -0,0 +1,170 @@
+// The inventories can come in three formats, which all end up as the calories of every elf's items:
+//
+// - The puzzle's own: the items of an elf on separate lines, with a blank line between elves.
+// - CSV with an elf and the calories of one item on every line (`elf,calories`), with or without
+//   that header. The items of an elf don't have to be next to each other, the elves are in the
+//   order they first show up in.
+// - JSON, an array with an array of items for every elf: `[[1000, 2000], [3000]]`.
+//
+// `detect()` guesses the format from the input, the day 1 binary can also be told which one it is.
+
+use std::str::FromStr;
+
+use common::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Blank,
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blank" => Ok(Format::Blank),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {s:?} (expected blank, csv or json)")),
+        }
+    }
+}
+
+// JSON starts with a `[`, and only CSV has commas.
+pub fn detect(input: &str) -> Format {
+    let input = input.trim_start();
+    if input.starts_with('[') {
+        Format::Json
+    } else if input.lines().next().unwrap_or_default().contains(',') {
+        Format::Csv
+    } else {
+        Format::Blank
+    }
+}
+
+pub fn parse(input: &str, format: Format) -> Result<Vec<Vec<u64>>, ParseError> {
+    match format {
+        Format::Blank => parse_blank(input),
+        Format::Csv => parse_csv(input),
+        Format::Json => parse_json(input),
+    }
+}
+
+fn calories(input: &str, s: &str) -> Result<u64, ParseError> {
+    s.parse::<u64>().map_err(|_| ParseError::at(input, s, "a number of calories"))
+}
+
+// This used to split the input at every `\n\n`. Then two blank lines in a row worked (the extra
+// `\n` was trimmed), but three didn't, and the streaming solution rejected both. Now any number of
+// blank lines ends an elf, line by line like `Day1::solve_stream()` does.
+fn parse_blank(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    let mut elves = vec![];
+    let mut items = vec![];
+    for line in input.lines() {
+        // The streaming solution doesn't see trailing whitespace either.
+        let line = line.trim_end();
+        if line.is_empty() {
+            if !items.is_empty() {
+                elves.push(std::mem::take(&mut items));
+            }
+        } else {
+            items.push(calories(input, line)?);
+        }
+    }
+    if !items.is_empty() {
+        elves.push(items);
+    }
+    if elves.is_empty() {
+        return Err(ParseError::at(input, &input[input.len()..], "a number of calories"));
+    }
+    Ok(elves)
+}
+
+fn parse_csv(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    // The names of the elves, in the same order as their items.
+    let mut names: Vec<&str> = vec![];
+    let mut elves: Vec<Vec<u64>> = vec![];
+    for (i, line) in input.lines().enumerate() {
+        if i == 0 && line.replace(' ', "").eq_ignore_ascii_case("elf,calories") {
+            continue;
+        }
+        let (name, value) = line
+            .split_once(',')
+            .ok_or_else(|| ParseError::at(input, line, "a line like `elf,calories`"))?;
+        let (name, value) = (name.trim(), value.trim());
+        if name.is_empty() {
+            return Err(ParseError::at(input, name, "the name of an elf"));
+        }
+        let value = calories(input, value)?;
+        // A linear search, but there aren't that many elves.
+        match names.iter().position(|&n| n == name) {
+            Some(elf) => elves[elf].push(value),
+            None => {
+                names.push(name);
+                elves.push(vec![value]);
+            }
+        }
+    }
+    if elves.is_empty() {
+        return Err(ParseError::at(input, &input[input.len()..], "a line like `elf,calories`"));
+    }
+    Ok(elves)
+}
+
+fn parse_json(input: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    let elves: Vec<Vec<u64>> = serde_json::from_str(input).map_err(|err| json_error(input, err))?;
+    // Like the other formats, there has to be at least one elf (their items can be empty).
+    if elves.is_empty() {
+        return Err(ParseError::at(input, input.trim(), "an array with at least one elf"));
+    }
+    Ok(elves)
+}
+
+// serde_json knows the line and column of an error, but we need a slice of the input for our
+// error. Its message ends with the position, which we already show in a different way.
+fn json_error(input: &str, err: serde_json::Error) -> ParseError {
+    let line = input.lines().nth(err.line().saturating_sub(1)).unwrap_or(&input[input.len()..]);
+    let mut column = err.column().saturating_sub(1).min(line.len());
+    while !line.is_char_boundary(column) {
+        column -= 1;
+    }
+    let message = err.to_string();
+    let message = message.split(" at line ").next().unwrap_or_default();
+    let expected = format!("an array of arrays of calories ({message})");
+    ParseError::at(input, &line[column..column], expected)
+}
+
+#[test]
+fn test_detect() {
+    assert_eq!(detect("1000\n2000\n\n3000\n"), Format::Blank);
+    assert_eq!(detect("elf,calories\n1,1000\n"), Format::Csv);
+    assert_eq!(detect("  [[1000]]"), Format::Json);
+    assert_eq!("csv".parse(), Ok(Format::Csv));
+    assert!("xml".parse::<Format>().is_err());
+}
+
+#[test]
+fn test_csv() {
+    let input = "elf,calories\nalice,1000\nbob,4000\nalice, 2000\ncarol,0\n";
+    assert_eq!(parse(input, Format::Csv).unwrap(), [vec![1000, 2000], vec![4000], vec![0]]);
+    // Without a header.
+    assert_eq!(parse("1,5\n2,6\n", Format::Csv).unwrap(), [vec![5], vec![6]]);
+    let err = parse("alice,1000\nbob\n", Format::Csv).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+    let err = parse("alice,-5\n", Format::Csv).unwrap_err();
+    assert_eq!((err.line, err.column, err.found.as_str()), (1, 7, "-5"));
+}
+
+#[test]
+fn test_json() {
+    let input = "[[1000, 2000],\n [], [18446744073709551615]]\n";
+    let elves = parse(input, Format::Json).unwrap();
+    assert_eq!(elves, [vec![1000, 2000], vec![], vec![u64::MAX]]);
+    let err = parse("[[1000, 2000],\n [30x0]]\n", Format::Json).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 5));
+    assert!(err.expected.starts_with("an array of arrays of calories"), "{}", err.expected);
+    let err = parse(" []\n", Format::Json).unwrap_err();
+    assert_eq!((err.line, err.column, err.found.as_str()), (1, 2, "[]"));
+}
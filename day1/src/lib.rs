@@ -0,0 +1,249 @@
+use std::io::BufRead;
+
+use common::{LineReader, ParseError, Solution, StreamError, StreamSolution};
+
+pub mod formats;
+pub mod stats;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    // The calories of the items carried by each elf.
+    // This used to be i32, which is plenty for the puzzle, but generated inputs overflowed it.
+    // Calories can't be negative, and u64 items add up to totals that always fit into a u128
+    // (even the top three together).
+    type Input = Vec<Vec<u64>>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    // The inventory can also be CSV or JSON (see `formats.rs`).
+    fn parse_normalized(content: &str) -> Result<Self::Input, ParseError> {
+        formats::parse(content, formats::detect(content))
+    }
+
+    // Without any elves (which parse() doesn't allow, but the type does) the answers are 0.
+    fn part1(elves: &Self::Input) -> u128 {
+        top_elves(elves, 1).iter().map(|elf| elf.total).sum()
+    }
+
+    fn part2(elves: &Self::Input) -> u128 {
+        top_elves(elves, 3).iter().map(|elf| elf.total).sum()
+    }
+}
+
+// An elf with everything we know about them.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Elf<'a> {
+    // The position in the input, starting at 1.
+    pub index: usize,
+    pub items: &'a [u64],
+    pub total: u128,
+}
+
+// Returns the `n` elves carrying the most calories, most first. Elves with the same total are in
+// the order of the input. If there are fewer than `n` elves, we get all of them.
+//
+// Sorting all elves to get a few of them is more work than needed. select_nth_unstable_by() moves
+// the top `n` to the front in linear time (without sorting them), so only those have to be
+// sorted.
+pub fn top_elves(elves: &[Vec<u64>], n: usize) -> Vec<Elf<'_>> {
+    let mut all: Vec<Elf> = elves
+        .iter()
+        .enumerate()
+        .map(|(i, items)| Elf {
+            index: i + 1,
+            items,
+            total: total(items),
+        })
+        .collect();
+    // Most calories first, then the lower index.
+    let order = |a: &Elf, b: &Elf| b.total.cmp(&a.total).then(a.index.cmp(&b.index));
+    if n == 0 {
+        return vec![];
+    }
+    if n < all.len() {
+        all.select_nth_unstable_by(n - 1, order);
+        all.truncate(n);
+    }
+    all.sort_by(order);
+    all
+}
+
+pub fn total(items: &[u64]) -> u128 {
+    items.iter().map(|&item| item as u128).sum()
+}
+
+// Both answers in a single pass over the input, keeping only the three largest totals instead of
+// all the elves.
+impl StreamSolution for Day1 {
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(u128, u128), StreamError> {
+        let mut lines = LineReader::new(reader);
+        let mut top = vec![];
+        // The total of the current elf, `None` until we've seen their first item.
+        let mut total = None;
+        while let Some((number, line)) = lines.next_line()? {
+            if line.is_empty() {
+                // A blank line ends an elf. More of them in a row (or at the start) don't make
+                // elves without items, the same as in `parse()`.
+                if let Some(total) = total.take() {
+                    insert_top(&mut top, total);
+                }
+            } else {
+                let calories: u64 = line.parse().map_err(|_| {
+                    ParseError::at(line, line, "a number of calories").on_line(number)
+                })?;
+                total = Some(total.unwrap_or(0) + calories as u128);
+            }
+        }
+        // The last elf isn't followed by a blank line. There is none if the input is empty.
+        let total = total.ok_or_else(|| ParseError::at("", "", "a number of calories"))?;
+        insert_top(&mut top, total);
+        Ok((top[0], top.iter().sum()))
+    }
+}
+
+// Adds a total to the three largest ones (largest first), if it's large enough.
+fn insert_top(top: &mut Vec<u128>, total: u128) {
+    let i = top.iter().position(|&t| t < total).unwrap_or(top.len());
+    top.insert(i, total);
+    top.truncate(3);
+}
+
+#[test]
+fn test_example() {
+    let input = Day1::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day1::part1(&input), 24000);
+    assert_eq!(Day1::part2(&input), 45000);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day1::parse(INPUT).unwrap();
+    assert_eq!(Day1::part1(&input), 70764);
+    assert_eq!(Day1::part2(&input), 203905);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day1>(INPUT);
+}
+
+// The same inventory in all three formats, and totals that don't fit into 64 bits.
+#[test]
+fn test_formats() {
+    let blank = Day1::parse(include_str!("../example.txt")).unwrap();
+    let csv = "elf,calories\n1,1000\n1,2000\n1,3000\n2,4000\n3,5000\n3,6000\n4,7000\n4,8000\n\
+               4,9000\n5,10000\n";
+    assert_eq!(Day1::parse(csv).unwrap(), blank);
+    let json = "[[1000, 2000, 3000], [4000], [5000, 6000], [7000, 8000, 9000], [10000]]";
+    assert_eq!(Day1::parse(json).unwrap(), blank);
+
+    let huge = format!("{0}\n{0}\n\n{0}\n", u64::MAX);
+    let elves = Day1::parse(&huge).unwrap();
+    assert_eq!(Day1::part1(&elves), 2 * u64::MAX as u128);
+    assert_eq!(Day1::part2(&elves), 3 * u64::MAX as u128);
+}
+
+#[test]
+fn test_top_elves() {
+    let elves = Day1::parse(include_str!("../example.txt")).unwrap();
+    let top = top_elves(&elves, 2);
+    assert_eq!(
+        top,
+        [
+            Elf { index: 4, items: &[7000, 8000, 9000], total: 24000 },
+            Elf { index: 3, items: &[5000, 6000], total: 11000 },
+        ]
+    );
+    // Asking for more elves than there are gives all of them.
+    let all: Vec<usize> = top_elves(&elves, 10).iter().map(|elf| elf.index).collect();
+    assert_eq!(all, [4, 3, 5, 1, 2]);
+    assert_eq!(top_elves(&elves, 0), []);
+}
+
+#[test]
+fn test_empty_groups() {
+    // Elves without items carry nothing, and the order of ties is the order of the input.
+    let elves = vec![vec![], vec![5], vec![], vec![5]];
+    let top: Vec<(usize, u128)> = top_elves(&elves, 3).iter().map(|e| (e.index, e.total)).collect();
+    assert_eq!(top, [(2, 5), (4, 5), (1, 0)]);
+    assert_eq!(Day1::part1(&vec![]), 0);
+    assert_eq!(Day1::part2(&vec![vec![], vec![1]]), 1);
+}
+
+// Reading the input bit by bit gives the same answers as parsing all of it first.
+#[test]
+fn test_stream() {
+    // More than one blank line between elves (or before the first one) is fine for both.
+    let blank_lines = "\n100\n\n\n200\n\n\n\n3 \n";
+    assert_eq!(Day1::parse(blank_lines).unwrap(), [vec![100], vec![200], vec![3]]);
+    for input in [include_str!("../example.txt"), INPUT, "1\n2\n\n3\n", blank_lines] {
+        let parsed = Day1::parse(input).unwrap();
+        let expected = (Day1::part1(&parsed), Day1::part2(&parsed));
+        assert_eq!(Day1::solve_stream(input.as_bytes()).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_stream_errors() {
+    let err = |input: &str| match Day1::solve_stream(input.as_bytes()) {
+        Err(StreamError::Parse(err)) => (err.line, err.column),
+        _ => panic!("no parse error for {input:?}"),
+    };
+    assert_eq!(err("100\nabc\n"), (2, 1));
+    assert_eq!(err(""), (1, 1));
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random inventories: up to 20 elves, each carrying 1 to 10 items.
+#[cfg(test)]
+fn arb_elves() -> impl Strategy<Value = Vec<Vec<u64>>> {
+    use proptest::collection::vec;
+    vec(vec(1..100_000u64, 1..10), 1..20)
+}
+
+#[cfg(test)]
+fn format_elves(elves: &[Vec<u64>]) -> String {
+    let elves: Vec<String> = elves.iter().map(|items| {
+        items.iter().map(|item| format!("{item}\n")).collect()
+    }).collect();
+    elves.join("\n")
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties(elves in arb_elves()) {
+        let text = format_elves(&elves);
+        let input = Day1::parse(&text).unwrap();
+        prop_assert_eq!(&input, &elves);
+        let part1 = Day1::part1(&input);
+        let part2 = Day1::part2(&input);
+        prop_assert_eq!(Day1::solve_stream(text.as_bytes()).unwrap(), (part1, part2));
+        // The top three include the top one, and none of them carries more than it.
+        prop_assert!(part2 >= part1);
+        prop_assert!(part2 <= 3 * part1);
+        if elves.len() == 1 {
+            prop_assert_eq!(part1, part2);
+        }
+    }
+
+    // Selecting the top elves gives the same as sorting all of them.
+    #[test]
+    fn test_top_elves_sorted(elves in arb_elves(), n in 0..25usize) {
+        let mut sorted: Vec<(std::cmp::Reverse<u128>, usize)> = elves
+            .iter()
+            .enumerate()
+            .map(|(i, items)| (std::cmp::Reverse(total(items)), i + 1))
+            .collect();
+        sorted.sort();
+        let expected: Vec<usize> = sorted.iter().take(n).map(|&(_, index)| index).collect();
+        let top: Vec<usize> = top_elves(&elves, n).iter().map(|elf| elf.index).collect();
+        prop_assert_eq!(top, expected);
+    }
+}
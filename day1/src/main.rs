@@ -1,25 +1,94 @@
-use std::fs;
+// Prints both answers like the other days. With `--top <n>`, it also shows the `n` elves carrying
+// the most calories, with their items:
+//
+//     cargo run -p day1 -- --top 5 [input]
+//
+// `--stats` shows statistics about the inventories instead (see `stats.rs`). The format of the
+// input is detected, unless it's given with `--format <blank|csv|json>` (see `formats.rs`).
 
-fn main() {
-    let content = fs::read_to_string("input.txt").expect("Failed to read from input.txt");
-    let chunks = content.split("\n\n").collect::<Vec<&str>>();
+use std::env;
+use std::process;
 
-    let max = chunks.iter().map(
-        |c| c.trim().split('\n').map(
-            |s| s.parse::<i32>().unwrap()
-        ).sum::<i32>()
-    ).max().unwrap();
+use common::Solution;
+use day1::formats::{self, Format};
+use day1::stats::Stats;
+use day1::{top_elves, Day1};
 
-    println!("part 1: {}", max);
+fn main() {
+    let mut args = env::args().skip(1);
+    let mut top = None;
+    let mut stats = false;
+    let mut format = None;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => {
+                let value = args.next().unwrap_or_default();
+                match value.parse::<usize>() {
+                    Ok(n) => top = Some(n),
+                    Err(_) => {
+                        eprintln!("error: --top needs a number of elves, not {value:?}");
+                        process::exit(2);
+                    }
+                }
+            }
+            "--stats" => stats = true,
+            "--format" => {
+                let value = args.next().unwrap_or_default();
+                match value.parse::<Format>() {
+                    Ok(f) => format = Some(f),
+                    Err(err) => {
+                        eprintln!("error: {err}");
+                        process::exit(2);
+                    }
+                }
+            }
+            _ => path = Some(arg),
+        }
+    }
+    let input = common::input::load_or_exit(path.as_deref(), day1::INPUT);
+    let elves = match format {
+        // Day1::parse() normalizes the input before it detects the format, so we have to as well.
+        Some(format) => {
+            let input = common::input::normalize(&input);
+            formats::parse(&input, format).unwrap_or_else(|err| {
+                eprintln!("error: failed to parse input: {err}");
+                process::exit(1);
+            })
+        }
+        None => common::parse_or_exit::<Day1>(&input),
+    };
 
-    let mut totals = chunks.iter().map(
-        |c| c.trim().split('\n').map(
-            |s| s.parse::<i32>().unwrap()
-        ).sum::<i32>()
-    ).collect::<Vec<i32>>();
+    if stats {
+        // The parsers don't allow inputs without elves, but better safe than sorry.
+        match Stats::new(&elves) {
+            Some(stats) => println!("{stats}"),
+            None => {
+                eprintln!("error: there are no elves to make statistics of");
+                process::exit(1);
+            }
+        }
+        return;
+    }
 
-    totals.sort_by(|a, b| b.cmp(a));
-    let sum_top3 = totals[..3].iter().sum::<i32>();
+    println!("part 1: {}", Day1::part1(&elves));
+    println!("part 2: {}", Day1::part2(&elves));
 
-    println!("part 2: {}", sum_top3);
+    if let Some(n) = top {
+        let top = top_elves(&elves, n);
+        if top.len() < n {
+            println!("\nthere are only {} elves", top.len());
+        }
+        println!("\ntop {} elves:", top.len());
+        for (rank, elf) in top.iter().enumerate() {
+            let items: Vec<String> = elf.items.iter().map(|item| item.to_string()).collect();
+            println!(
+                "{:>3}. elf {}: {} calories ({})",
+                rank + 1,
+                elf.index,
+                elf.total,
+                items.join(" + ")
+            );
+        }
+    }
 }
@@ -0,0 +1,168 @@
+// Statistics about the inventories, for `--stats`. Not needed for the puzzle, but it's nice to
+// see what the input looks like:
+//
+//     elves: 5
+//     items per elf: 1 to 3 (1.8 on average)
+//     calories per elf:
+//       min        4000.0
+//       p10        4800.0
+//     ...
+
+use std::fmt;
+
+pub struct Stats {
+    // The number of items and the total calories of every elf, in the order of the input.
+    pub items: Vec<usize>,
+    pub totals: Vec<u128>,
+    // The totals again, sorted, for the median and percentiles.
+    sorted: Vec<u128>,
+    pub mean: f64,
+    pub median: f64,
+    // The (1-based) index of every elf whose total is far away from the others (see `outliers()`).
+    pub outliers: Vec<usize>,
+}
+
+impl Stats {
+    // There are no statistics without elves.
+    pub fn new(elves: &[Vec<u64>]) -> Option<Stats> {
+        if elves.is_empty() {
+            return None;
+        }
+        let items: Vec<usize> = elves.iter().map(|items| items.len()).collect();
+        let totals: Vec<u128> = elves.iter().map(|items| crate::total(items)).collect();
+        let mut sorted = totals.clone();
+        sorted.sort();
+        // Adding up as floats, so all the totals together can't overflow.
+        let mean = totals.iter().map(|&t| t as f64).sum::<f64>() / totals.len() as f64;
+        let mut stats = Stats {
+            items,
+            totals,
+            sorted,
+            mean,
+            median: 0.0,
+            outliers: vec![],
+        };
+        stats.median = stats.percentile(50.0);
+        stats.outliers = stats.outliers();
+        Some(stats)
+    }
+
+    // The total that `p` percent of the elves are at or below. Between two elves, it's
+    // interpolated: with 5 elves, the 10th percentile is 40% of the way from the lowest to the
+    // second lowest.
+    pub fn percentile(&self, p: f64) -> f64 {
+        let rank = p / 100.0 * (self.sorted.len() - 1) as f64;
+        let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+        let (low, high) = (self.sorted[below] as f64, self.sorted[above] as f64);
+        low + (high - low) * (rank - below as f64)
+    }
+
+    // Tukey's fences: totals more than 1.5 times the interquartile range (the range of the middle
+    // half of the elves) below the first or above the third quartile.
+    fn outliers(&self) -> Vec<usize> {
+        let (q1, q3) = (self.percentile(25.0), self.percentile(75.0));
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+        self.totals
+            .iter()
+            .enumerate()
+            .filter(|(_, &total)| (total as f64) < low || (total as f64) > high)
+            .map(|(i, _)| i + 1)
+            .collect()
+    }
+
+    // The number of elves in `buckets` equally wide ranges of totals, from the lowest to the
+    // highest total, with a bar for each. The longest bar is `width` characters long.
+    pub fn histogram(&self, buckets: usize, width: usize) -> String {
+        let (min, max) = (self.sorted[0], *self.sorted.last().unwrap());
+        // If all elves carry the same, one bucket is all we need.
+        let buckets = if min == max { 1 } else { buckets.max(1) };
+        let size = (max - min) as f64 / buckets as f64;
+        let mut counts = vec![0; buckets];
+        for &total in &self.totals {
+            let bucket = ((total - min) as f64 / size) as usize;
+            counts[bucket.min(buckets - 1)] += 1;
+        }
+        let most = *counts.iter().max().unwrap();
+        let mut histogram = String::new();
+        for (i, count) in counts.iter().enumerate() {
+            let from = min + (size * i as f64) as u128;
+            let to = if i == buckets - 1 { max } else { min + (size * (i + 1) as f64) as u128 };
+            let bar = "#".repeat((count * width).div_ceil(most));
+            histogram += &format!("{from:>8} - {to:>8} | {bar} {count}\n");
+        }
+        histogram
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let elves = self.totals.len();
+        writeln!(f, "elves: {elves}")?;
+        let (min, max) = (self.items.iter().min().unwrap(), self.items.iter().max().unwrap());
+        let mean = self.items.iter().sum::<usize>() as f64 / elves as f64;
+        writeln!(f, "items per elf: {min} to {max} ({mean:.1} on average)")?;
+        writeln!(f, "calories per elf:")?;
+        let rows = [
+            ("min", self.percentile(0.0)),
+            ("p10", self.percentile(10.0)),
+            ("p25", self.percentile(25.0)),
+            ("median", self.median),
+            ("mean", self.mean),
+            ("p75", self.percentile(75.0)),
+            ("p90", self.percentile(90.0)),
+            ("max", self.percentile(100.0)),
+        ];
+        for (name, value) in rows {
+            writeln!(f, "  {name:<6} {value:>10.1}")?;
+        }
+        writeln!(f, "histogram:")?;
+        write!(f, "{}", self.histogram(10, 40))?;
+        if self.outliers.is_empty() {
+            write!(f, "no outliers")
+        } else {
+            let outliers: Vec<String> = self
+                .outliers
+                .iter()
+                .map(|&i| format!("elf {i} ({})", self.totals[i - 1]))
+                .collect();
+            write!(f, "outliers: {}", outliers.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+fn example() -> Stats {
+    use common::Solution;
+    let elves = crate::Day1::parse(include_str!("../example.txt")).unwrap();
+    Stats::new(&elves).unwrap()
+}
+
+#[test]
+fn test_stats() {
+    let stats = example();
+    assert_eq!(stats.items, [3, 1, 2, 3, 1]);
+    assert_eq!(stats.mean, 11000.0);
+    assert_eq!(stats.median, 10000.0);
+    assert_eq!(stats.percentile(10.0), 4800.0);
+    assert_eq!(stats.percentile(90.0), 18800.0);
+    // The quartiles are 6000 and 11000, so everything above 18500 is an outlier.
+    assert_eq!(stats.outliers, [4]);
+    assert!(Stats::new(&[]).is_none());
+}
+
+#[test]
+fn test_histogram() {
+    assert_eq!(
+        example().histogram(4, 10),
+        "    4000 -     9000 | ########## 2
+    9000 -    14000 | ########## 2
+   14000 -    19000 |  0
+   19000 -    24000 | ##### 1
+"
+    );
+    // Everyone carries the same, and an elf without items carries nothing.
+    let stats = Stats::new(&[vec![], vec![], vec![]]).unwrap();
+    assert_eq!(stats.histogram(10, 10), "       0 -        0 | ########## 3\n");
+    assert!(stats.outliers.is_empty());
+}
@@ -0,0 +1,362 @@
+use std::fmt;
+use std::io::BufRead;
+
+use common::{LineReader, ParseError, Solution, StreamError, StreamSolution};
+use viz::{Frame, Image, Rgb, Style};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+// x is an i64, although the values of `addx` are i32s: adding them up can leave the range of an
+// i32 after two instructions. `parse()` checks that it stays within an i64.
+pub struct Cpu {
+    pub x: i64,
+    program: Vec<Instruction>,
+    ip: usize,
+    counter: i32,
+}
+
+impl Cpu {
+    pub fn new(program: Vec<Instruction>) -> Self {
+        Self {
+            x: 1,
+            program,
+            ip: 0,
+            counter: 0,
+        }
+    }
+
+    pub fn cycle(&mut self) {
+        if self.done() {
+            return;
+        }
+
+        let instruction = &self.program[self.ip];
+        if self.counter == 0 {
+            self.counter = match instruction {
+                Instruction::Noop => 1,
+                Instruction::Addx(_) => 2,
+            }
+        }
+        self.counter -= 1;
+        if self.counter == 0 {
+            self.x = match instruction {
+                Instruction::Noop => self.x,
+                Instruction::Addx(value) => self.x + i64::from(*value),
+            };
+            self.ip += 1;
+        }
+    }
+
+    pub fn done(&self) -> bool {
+        self.ip >= self.program.len()
+    }
+}
+
+pub struct Crt {
+    pixels: [[char; 40]; 6],
+    h: usize,
+    v: usize,
+}
+
+impl Crt {
+    pub fn new() -> Self {
+        Crt {
+            pixels: [['.'; 40]; 6],
+            h: 0,
+            v: 0,
+        }
+    }
+
+    pub fn cycle(&mut self, input: i64) {
+        // abs_diff() can't overflow, however far away the sprite is.
+        if input.abs_diff(self.h as i64) < 2 {
+            self.pixels[self.v][self.h] = '#'
+        } else {
+            self.pixels[self.v][self.h] = '.'
+        }
+        self.h = (self.h + 1) % 40;
+        if self.h == 0 {
+            self.v = (self.v + 1) % 6;
+        }
+    }
+
+    // The screen as a frame for the renderer, with the lit pixels standing out.
+    pub fn frame(&self) -> Frame {
+        let mut frame = Frame::new();
+        for row in &self.pixels {
+            for &pixel in row {
+                let style = if pixel == '#' { Style::Bright } else { Style::Dim };
+                frame.push(pixel, style);
+            }
+            frame.newline();
+        }
+        frame
+    }
+
+    // One pixel per pixel, in the colours of an old green monitor.
+    pub fn image(&self) -> Image {
+        Image::from_fn(40, 6, |x, y| match self.pixels[y][x] {
+            '#' => Rgb(80, 255, 120),
+            _ => Rgb(10, 30, 15),
+        })
+    }
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Crt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = String::new();
+        for y in 0..6 {
+            for x in 0..40 {
+                s.push(self.pixels[y][x]);
+            }
+            s.push('\n');
+        }
+        write!(f, "{}", s)
+    }
+}
+
+fn parse_instruction(input: &str, line: &str) -> Result<Instruction, ParseError> {
+    if line == "noop" {
+        Ok(Instruction::Noop)
+    } else if let Some(value) = line.strip_prefix("addx ") {
+        let value = value
+            .parse()
+            .map_err(|_| ParseError::at(input, value, "a number"))?;
+        Ok(Instruction::Addx(value))
+    } else {
+        Err(ParseError::at(
+            input,
+            line,
+            "an instruction (`noop` or `addx <value>`)",
+        ))
+    }
+}
+
+// The value of x after an instruction, or an error if it doesn't fit into an i64. That takes
+// billions of large `addx`s, but a generated input can have them.
+fn execute(input: &str, line: &str, x: i64, instruction: Instruction) -> Result<i64, ParseError> {
+    match instruction {
+        Instruction::Noop => Ok(x),
+        Instruction::Addx(value) => x
+            .checked_add(i64::from(value))
+            .ok_or_else(|| ParseError::at(input, line, "an `addx` that keeps x within 64 bits")),
+    }
+}
+
+fn read_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut x = 1;
+    let mut instructions = vec![];
+    for line in input.lines() {
+        let instruction = parse_instruction(input, line)?;
+        x = execute(input, line, x, instruction)?;
+        instructions.push(instruction);
+    }
+    Ok(instructions)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    type Answer1 = i64;
+    // The answer is whatever letters show up on the screen, so we return the screen.
+    type Answer2 = Crt;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    // Only the first 220 cycles count, so x and the products are far from overflowing an i64.
+    fn part1(instructions: &Self::Input) -> i64 {
+        let mut cpu = Cpu::new(instructions.clone());
+        let mut result = 0;
+        for n in 1..221 {
+            if SAMPLES.contains(&n) {
+                result += n as i64 * cpu.x;
+            }
+            cpu.cycle();
+        }
+        result
+    }
+
+    fn part2(instructions: &Self::Input) -> Crt {
+        let mut cpu = Cpu::new(instructions.clone());
+        let mut crt = Crt::new();
+        while !cpu.done() {
+            crt.cycle(cpu.x);
+            cpu.cycle();
+        }
+        crt
+    }
+}
+
+// The cycles are u64s, because a long enough stream has more than `i32::MAX` of them.
+const SAMPLES: [u64; 6] = [20, 60, 100, 140, 180, 220];
+
+// The CPU needs the whole program, so for streaming we run the instructions as they come in: every
+// instruction takes one or two cycles, and x only changes after them. The screen is only 40x6
+// pixels, however long the program is.
+impl StreamSolution for Day10 {
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(i64, Crt), StreamError> {
+        let mut lines = LineReader::new(reader);
+        let mut crt = Crt::new();
+        let mut x = 1;
+        let mut cycle: u64 = 0;
+        let mut strength = 0;
+        while let Some((number, line)) = lines.next_line()? {
+            let instruction = parse_instruction(line, line).map_err(|err| err.on_line(number))?;
+            let cycles = match instruction {
+                Instruction::Noop => 1,
+                Instruction::Addx(_) => 2,
+            };
+            for _ in 0..cycles {
+                cycle += 1;
+                if SAMPLES.contains(&cycle) {
+                    strength += cycle as i64 * x;
+                }
+                crt.cycle(x);
+            }
+            x = execute(line, line, x, instruction).map_err(|err| err.on_line(number))?;
+        }
+        // Part 1 keeps going after the end of a short program, with the last value of x.
+        for sample in SAMPLES {
+            if sample > cycle {
+                strength += sample as i64 * x;
+            }
+        }
+        Ok((strength, crt))
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day10::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day10::part1(&input), 13140);
+    assert_eq!(
+        Day10::part2(&input).to_string(),
+        "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"
+    );
+}
+
+// The screen after running the example, saved with
+// `aoc image 10 day10/example.ppm --input day10/example.txt`.
+#[test]
+fn test_image() {
+    let input = Day10::parse(include_str!("../example.txt")).unwrap();
+    let expected = Image::from_ppm(include_bytes!("../example.ppm")).unwrap();
+    assert_eq!(Day10::part2(&input).image(), expected);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day10::parse(INPUT).unwrap();
+    assert_eq!(Day10::part1(&input), 12740);
+    // RBPARAGF
+    assert_eq!(
+        Day10::part2(&input).to_string(),
+        "\
+###..###..###...##..###...##...##..####.
+#..#.#..#.#..#.#..#.#..#.#..#.#..#.#....
+#..#.###..#..#.#..#.#..#.#..#.#....###..
+###..#..#.###..####.###..####.#.##.#....
+#.#..#..#.#....#..#.#.#..#..#.#..#.#....
+#..#.###..#....#..#.#..#.#..#..###.#....
+"
+    );
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day10>(INPUT);
+}
+
+// Reading the input bit by bit gives the same answers as parsing all of it first.
+#[test]
+fn test_stream() {
+    for input in [include_str!("../example.txt"), INPUT, "addx 5\nnoop\n"] {
+        let parsed = Day10::parse(input).unwrap();
+        let (part1, crt) = Day10::solve_stream(input.as_bytes()).unwrap();
+        assert_eq!(part1, Day10::part1(&parsed));
+        assert_eq!(crt.to_string(), Day10::part2(&parsed).to_string());
+    }
+}
+
+// The values of `addx` are i32s, but x and the signal strengths don't have to fit into one.
+#[test]
+fn test_large_values() {
+    let samples = SAMPLES.iter().sum::<u64>() as i64;
+    for (input, x) in [
+        ("addx 2147483647\naddx 1\nnoop\n", 2147483649),
+        ("addx -2147483648\nnoop\n", -2147483647),
+    ] {
+        let parsed = Day10::parse(input).unwrap();
+        assert_eq!(Day10::part1(&parsed), samples * x);
+        assert_eq!(Day10::solve_stream(input.as_bytes()).unwrap().0, samples * x);
+        // Only the first two pixels, before the first `addx` moved the sprite far away.
+        assert!(Day10::part2(&parsed).to_string().starts_with("##..."));
+    }
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+#[cfg(test)]
+fn arb_program() -> impl Strategy<Value = String> {
+    let instruction = prop_oneof![
+        Just("noop\n".to_string()),
+        (-50..50i32).prop_map(|value| format!("addx {value}\n")),
+        // The largest values there are, which don't fit into an i32 when added up.
+        any::<i32>().prop_map(|value| format!("addx {value}\n")),
+    ];
+    proptest::collection::vec(instruction, 0..200).prop_map(|program| program.concat())
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties(program in arb_program()) {
+        let instructions = Day10::parse(&program).unwrap();
+        // The value of x during every cycle, the simple way: noop takes one cycle, addx two, and
+        // x only changes after them. Once the program is done x stays the same.
+        let mut x = 1i64;
+        let mut during = vec![0];
+        for instruction in &instructions {
+            match instruction {
+                Instruction::Noop => during.push(x),
+                Instruction::Addx(value) => {
+                    during.extend([x, x]);
+                    x += i64::from(*value);
+                }
+            }
+        }
+        let at = |n: usize| during.get(n).copied().unwrap_or(x);
+        let expected: i64 = [20, 60, 100, 140, 180, 220].iter().map(|&n| n as i64 * at(n)).sum();
+        prop_assert_eq!(Day10::part1(&instructions), expected);
+        prop_assert_eq!(Day10::solve_stream(program.as_bytes()).unwrap().0, expected);
+
+        // Every pixel is either on or off.
+        let screen = Day10::part2(&instructions).to_string();
+        prop_assert!(screen.lines().all(|line| line.len() == 40));
+        prop_assert!(screen.chars().all(|c| matches!(c, '#' | '.' | '\n')));
+    }
+}
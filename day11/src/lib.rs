@@ -0,0 +1,428 @@
+// For this day I used the external 'regex' crate to parse the input using regular expressions,
+// instead of only relying on split/strip_prefix etc.
+// Coming from Python, having to anticipate how large your numbers can get during calculations and
+// sizing your integer variables appropriately (u32, u64 etc.) was new to me.
+// Also, I was using `as u32` etc. for doing conversions between differnet sizes before, which is
+// dangerous, because when converting to a smaller size values will silently overflow. In general,
+// it is better to use the `::from` function from the `From` trait, which I started doing here. It
+// is only implemented for save conversions, or `TryFrom` if a conversion can fail.
+
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+
+use common::{ParseError, Solution};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Add(u32),
+    Multiply(u32),
+    Square,
+}
+
+impl Operation {
+    // The new worry level, or `None` if it doesn't fit into a u128.
+    fn apply(&self, old: u128) -> Option<u128> {
+        match *self {
+            Operation::Add(value) => old.checked_add(u128::from(value)),
+            Operation::Multiply(value) => old.checked_mul(u128::from(value)),
+            Operation::Square => old.checked_mul(old),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    items: Vec<u128>,
+    operation: Operation,
+    divisor: u32,
+    target: (usize, usize),
+    // Part two inspects every item 10000 times, so a few hundred thousand items are enough to
+    // count past `u32::MAX`.
+    inspected: u64,
+}
+
+fn read_input(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut monkeys = vec![];
+    let monkey_re = Regex::new(r"^Monkey \d+:$").unwrap();
+    let start_re = Regex::new(r"Starting items:(?: (\d+(?:, \d+)*))? *$").unwrap();
+    let op_re = Regex::new(r"Operation: new = old (.) (old|\d+)").unwrap();
+    let test_re = Regex::new(r"Test: divisible by (\d+)").unwrap();
+    let branch_re = Regex::new(r"If (true|false): throw to monkey (\d+)").unwrap();
+    // The `&str`s of a capture group are slices of the input, so we can still point at them when
+    // something is wrong with the numbers (like a value that is too large).
+    // This can't be a closure, because closures can't be generic over the number type.
+    fn number<T: FromStr>(input: &str, s: &str) -> Result<T, ParseError> {
+        s.parse().map_err(|_| ParseError::at(input, s, "a number"))
+    }
+    // We can only check the monkeys items are thrown to once we know how many monkeys there are.
+    let mut targets = vec![];
+    // The same goes for the product of the divisors.
+    let mut divisors = vec![];
+    for chunk in input.split("\n\n") {
+        let mut monkey = Monkey {
+            items: vec![],
+            operation: Operation::Add(0),
+            divisor: 0,
+            target: (0, 0),
+            inspected: 0,
+        };
+        let mut divisor_text = chunk;
+        for line in chunk.lines() {
+            if let Some(c) = start_re.captures(line) {
+                // A monkey can start without any items.
+                if let Some(items) = c.get(1) {
+                    monkey.items = items
+                        .as_str()
+                        .split(',')
+                        // u64s, so that part two can square them at first, too.
+                        .map(|s| number::<u64>(input, s.trim()).map(u128::from))
+                        .collect::<Result<_, _>>()?;
+                }
+            } else if let Some(c) = op_re.captures(line) {
+                let value = c.get(2).unwrap().as_str();
+                monkey.operation = match (&c[1], value) {
+                    ("*", "old") => Operation::Square,
+                    ("*", value) => Operation::Multiply(number(input, value)?),
+                    ("+", value) => Operation::Add(number(input, value)?),
+                    _ => {
+                        let op = c.get(1).unwrap().as_str();
+                        return Err(ParseError::at(input, op, "an operator (`+` or `*`)"));
+                    }
+                };
+            } else if let Some(c) = test_re.captures(line) {
+                let divisor = c.get(1).unwrap().as_str();
+                divisor_text = divisor;
+                monkey.divisor = number(input, divisor)?;
+                if monkey.divisor == 0 {
+                    return Err(ParseError::at(input, divisor, "a divisor other than 0"));
+                }
+            } else if let Some(c) = branch_re.captures(line) {
+                let target = c.get(2).unwrap().as_str();
+                targets.push(target);
+                if &c[1] == "true" {
+                    monkey.target.0 = number(input, target)?;
+                } else {
+                    monkey.target.1 = number(input, target)?;
+                }
+            } else if !monkey_re.is_match(line) && !line.is_empty() {
+                return Err(ParseError::at(input, line.trim(), "a line from the monkey notes"));
+            }
+        }
+        if monkey.divisor == 0 {
+            return Err(ParseError::at(
+                input,
+                chunk,
+                "a monkey with a `Test: divisible by <n>` line",
+            ));
+        }
+        monkeys.push(monkey);
+        divisors.push(divisor_text);
+    }
+    for target in targets {
+        if number::<usize>(input, target)? >= monkeys.len() {
+            return Err(ParseError::at(
+                input,
+                target,
+                format!("a monkey between 0 and {}", monkeys.len() - 1),
+            ));
+        }
+    }
+    // Part two keeps the worry levels below the product of the divisors. It has to fit into a
+    // u64, so that squaring a level still fits into a u128.
+    let mut product = 1u64;
+    for (monkey, divisor) in monkeys.iter().zip(divisors) {
+        product = product.checked_mul(u64::from(monkey.divisor)).ok_or_else(|| {
+            ParseError::at(input, divisor, "divisors whose product fits into 64 bits")
+        })?;
+    }
+    Ok(monkeys)
+}
+
+// Plays a round. `relief` is what happens to a worry level after the monkey inspected the item:
+// part one divides it by 3, part two keeps it small. If an operation makes a level too large for
+// a u128, the error is the index of that monkey.
+fn play_round(monkeys: &mut [Monkey], relief: &impl Fn(u128) -> u128) -> Result<(), usize> {
+    for i in 0..monkeys.len() {
+        let m = &mut monkeys[i];
+        let mut thrown = vec![];
+        for level in m.items.drain(..) {
+            m.inspected += 1;
+            let level = relief(m.operation.apply(level).ok_or(i)?);
+            let target = if level.is_multiple_of(u128::from(m.divisor)) {
+                m.target.0
+            } else {
+                m.target.1
+            };
+            thrown.push((target, level));
+        }
+        for (i, item) in thrown {
+            monkeys[i].items.push(item);
+        }
+    }
+    Ok(())
+}
+
+// Plays a number of rounds and returns how many items each monkey inspected.
+fn play(monkeys: &[Monkey], rounds: u32, relief: impl Fn(u128) -> u128) -> Result<Vec<u64>, usize> {
+    let mut monkeys = monkeys.to_vec();
+    for _ in 0..rounds {
+        play_round(&mut monkeys, &relief)?;
+    }
+    Ok(monkeys.iter().map(|m| m.inspected).collect())
+}
+
+// The level of monkey business: the product of the two highest counts, which always fits into a
+// u128.
+fn monkey_business(mut counts: Vec<u64>) -> u128 {
+    counts.sort_by(|a, b| b.cmp(a));
+    // take() instead of `[..2]`, which would panic if there is only one monkey.
+    counts.iter().take(2).map(|&count| u128::from(count)).product()
+}
+
+// The trick to solving part two is that `x mod a*b` is divisible by `a` if `x` was divisible by
+// `a`. So we can keep the levels modulo the product of all divisors (`parse()` checks that it fits
+// into a u64).
+fn modulus(monkeys: &[Monkey]) -> u128 {
+    monkeys.iter().map(|m| u128::from(m.divisor)).product()
+}
+
+// The answer of part one. Its worry levels are exact, and those can get too large even for a u128
+// (every `old * old` doubles their digits). Then there's no answer, only the monkey whose
+// operation made a level overflow. We only find out by playing the rounds, so this is the answer
+// instead of a parse error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonkeyBusiness {
+    Level(u128),
+    Overflow { monkey: usize },
+}
+
+impl fmt::Display for MonkeyBusiness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MonkeyBusiness::Level(level) => write!(f, "{level}"),
+            MonkeyBusiness::Overflow { monkey } => {
+                write!(f, "none, monkey {monkey} makes a worry level too large for 128 bits")
+            }
+        }
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    type Answer1 = MonkeyBusiness;
+    type Answer2 = u128;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(monkeys: &Self::Input) -> MonkeyBusiness {
+        match play(monkeys, 20, |level| level / 3) {
+            Ok(counts) => MonkeyBusiness::Level(monkey_business(counts)),
+            Err(monkey) => MonkeyBusiness::Overflow { monkey },
+        }
+    }
+
+    fn part2(monkeys: &Self::Input) -> u128 {
+        let modulus = modulus(monkeys);
+        // The levels stay below the modulus, a u64, so squaring them can't overflow.
+        monkey_business(play(monkeys, 10_000, |level| level % modulus).unwrap())
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day11::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day11::part1(&input), MonkeyBusiness::Level(10605));
+    assert_eq!(Day11::part2(&input), 2713310158);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day11::parse(INPUT).unwrap();
+    assert_eq!(Day11::part1(&input), MonkeyBusiness::Level(55216));
+    assert_eq!(Day11::part2(&input), 12848882750);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day11>(INPUT);
+}
+
+#[test]
+fn test_one_monkey() {
+    let input = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old + 3
+  Test: divisible by 23
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+    let monkeys = Day11::parse(input).unwrap();
+    assert_eq!(Day11::parse(&input.replace("79, 98", "")).unwrap()[0].items, vec![]);
+    // The monkey throws the items to itself, and inspects them again in the next round.
+    assert_eq!(Day11::part1(&monkeys), MonkeyBusiness::Level(2 * 20));
+    // Squaring them 20 times makes the levels of part one too large, even divided by 3. Part two
+    // still works.
+    let monkeys = Day11::parse(&input.replace("old + 3", "old * old")).unwrap();
+    let answer = Day11::part1(&monkeys);
+    assert_eq!(answer, MonkeyBusiness::Overflow { monkey: 0 });
+    assert_eq!(answer.to_string(), "none, monkey 0 makes a worry level too large for 128 bits");
+    assert_eq!(Day11::part2(&monkeys), 2 * 10_000);
+}
+
+// Part one has to use the exact levels. I used to keep them modulo `3 * divisor` before dividing
+// by 3, but the result only agrees with the real level modulo the divisor, not modulo 3. Here the
+// item then went to the wrong monkey after a few rounds, and the answer was 40.
+#[test]
+fn test_exact_levels() {
+    let input = "\
+Monkey 0:
+  Starting items: 87
+  Operation: new = old * 18
+  Test: divisible by 3
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items:
+  Operation: new = old + 7
+  Test: divisible by 3
+    If true: throw to monkey 1
+    If false: throw to monkey 0
+";
+    assert_eq!(Day11::part1(&Day11::parse(input).unwrap()), MonkeyBusiness::Level(340));
+}
+
+#[test]
+fn test_too_many_divisors() {
+    let monkey = |i| {
+        format!(
+            "Monkey {i}:\n  Starting items: 1\n  Operation: new = old + 1\n  \
+             Test: divisible by 4294967291\n    If true: throw to monkey 0\n    \
+             If false: throw to monkey 0\n"
+        )
+    };
+    let notes = [monkey(0), monkey(1)].join("\n");
+    assert!(Day11::parse(&notes).is_ok());
+    // The third divisor makes the product too large for part two.
+    let notes = [monkey(0), monkey(1), monkey(2)].join("\n");
+    assert_eq!(Day11::parse(&notes).unwrap_err().line, 18);
+}
+
+// A simulation without any tricks to compare with: the exact levels, item by item. Returns the
+// number of inspections per monkey, or `None` if a level gets too large.
+#[cfg(test)]
+fn reference(monkeys: &[Monkey], rounds: u32, divide_by_three: bool) -> Option<Vec<u64>> {
+    let mut items: Vec<Vec<u128>> = monkeys.iter().map(|m| m.items.clone()).collect();
+    let mut counts = vec![0; monkeys.len()];
+    for _ in 0..rounds {
+        for (i, m) in monkeys.iter().enumerate() {
+            for old in std::mem::take(&mut items[i]) {
+                counts[i] += 1;
+                let new = match m.operation {
+                    Operation::Add(value) => old.checked_add(u128::from(value))?,
+                    Operation::Multiply(value) => old.checked_mul(u128::from(value))?,
+                    Operation::Square => old.checked_mul(old)?,
+                };
+                let new = if divide_by_three { new / 3 } else { new };
+                let yes = new.is_multiple_of(u128::from(m.divisor));
+                items[if yes { m.target.0 } else { m.target.1 }].push(new);
+            }
+        }
+    }
+    Some(counts)
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random notes for 2 to 8 monkeys, with the kind of divisors (small primes) and operations of the
+// real input.
+#[cfg(test)]
+fn arb_notes() -> impl Strategy<Value = String> {
+    use proptest::collection::vec;
+    let primes = vec![2u32, 3, 5, 7, 11, 13, 17, 19, 23];
+    (2..=8usize).prop_flat_map(move |n| {
+        // Like in the real input, only a few monkeys square the levels, or part one's exact
+        // levels get too large for most notes.
+        let operation = prop_oneof![
+            1 => Just("old * old".to_string()),
+            4 => (1..20u32).prop_map(|v| format!("old * {v}")),
+            4 => (1..10u32).prop_map(|v| format!("old + {v}")),
+        ];
+        let monkey = (
+            vec(1..100u64, 0..6),
+            operation,
+            proptest::sample::select(primes.clone()),
+            0..n,
+            0..n,
+        );
+        vec(monkey, n).prop_map(|monkeys| {
+            let mut notes = vec![];
+            for (i, (items, operation, divisor, yes, no)) in monkeys.into_iter().enumerate() {
+                let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                notes.push(format!(
+                    "Monkey {i}:\n  Starting items: {}\n  Operation: new = {operation}\n  \
+                     Test: divisible by {divisor}\n    If true: throw to monkey {yes}\n    \
+                     If false: throw to monkey {no}\n",
+                    items.join(", ")
+                ));
+            }
+            notes.join("\n")
+        })
+    })
+}
+
+#[cfg(test)]
+proptest! {
+    // Part 2 plays 10000 rounds, so fewer cases than usual.
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn test_properties(notes in arb_notes()) {
+        let monkeys = Day11::parse(&notes).unwrap();
+        let items = monkeys.iter().map(|m| m.items.len() as u128).sum::<u128>();
+        // Every item is inspected at most once per monkey and round (an item can only go to a
+        // monkey that comes later in the same round).
+        let max = |rounds: u128| rounds * items * monkeys.len() as u128;
+        // Squaring the levels can make them too large for part one.
+        if let MonkeyBusiness::Level(level) = Day11::part1(&monkeys) {
+            prop_assert!(level <= max(20).pow(2));
+        }
+        prop_assert!(Day11::part2(&monkeys) <= max(10_000).pow(2));
+    }
+
+    #[test]
+    fn test_reference(notes in arb_notes()) {
+        let monkeys = Day11::parse(&notes).unwrap();
+        // Part one overflows exactly when the simulation without tricks does.
+        match reference(&monkeys, 20, true) {
+            Some(expected) => {
+                let expected = MonkeyBusiness::Level(monkey_business(expected));
+                prop_assert_eq!(Day11::part1(&monkeys), expected);
+            }
+            None => {
+                let overflow = matches!(Day11::part1(&monkeys), MonkeyBusiness::Overflow { .. });
+                prop_assert!(overflow);
+            }
+        }
+        // Part two's levels modulo the divisors send the items to the same monkeys as the exact
+        // ones, for as long as we can compute those.
+        let modulus = modulus(&monkeys);
+        for rounds in 1..=20 {
+            let Some(expected) = reference(&monkeys, rounds, false) else {
+                break;
+            };
+            prop_assert_eq!(play(&monkeys, rounds, |level| level % modulus), Ok(expected));
+        }
+    }
+}
@@ -0,0 +1,259 @@
+// This was relatively straightforward thanks to the BinaryHeap implementation in the Rust
+// stdlib for implementing Dijkstra's algorithm.
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+
+use common::{ParseError, Solution};
+use grid::Grid;
+use viz::{Frame, Image, Rgb, Style};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub use grid::Point;
+
+#[derive(Debug)]
+pub struct Map {
+    tiles: Grid<char>,
+    pub start: Point,
+    pub end: Point,
+}
+
+pub fn find_shortest_path(
+    map: &Map,
+    start: Point,
+    end: Point,
+    end_value: char,
+) -> Result<Vec<Point>, &str> {
+    let mut dist: HashMap<Point, i32> = HashMap::new();
+    let mut prev: HashMap<Point, Point> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    let mut value;
+    dist.insert(start, 0);
+    // BinaryHeap is a max-heap. We we want a min-heap. Wrapping all items in Reverse reverses the
+    // sort order.
+    queue.push(Reverse((0, start)));
+
+    fn build_path(prev: &HashMap<Point, Point>, node: Point) -> Vec<Point> {
+        let mut path = vec![];
+        let mut node = node;
+        path.push(node);
+        while let Some(next) = prev.get(&node) {
+            path.push(*next);
+            node = *next;
+        }
+        path
+    }
+
+    while let Some(Reverse((p, node))) = queue.pop() {
+        value = map.tiles[node];
+        if node == end || value == end_value {
+            return Ok(build_path(&prev, node));
+        }
+        let d = *dist.get(&(node)).unwrap();
+        if p != d {
+            continue;
+        }
+        for (next, &next_value) in map.tiles.neighbours4(node) {
+            // Flipped the condition around for the part two,
+            // where we need to search in the opposite direction
+            // (which also works for part 1).
+            // `as i32` used here because of lazyness.
+            if (value as i32 - next_value as i32) <= 1 {
+                let alt = d + 1;
+                if alt < *dist.get(&next).unwrap_or(&i32::MAX) {
+                    dist.insert(next, alt);
+                    prev.insert(next, node);
+                    queue.push(Reverse((alt, next)));
+                }
+            }
+        }
+    }
+
+    Err("failed to find path")
+}
+
+fn read_input(input: &str) -> Result<Map, ParseError> {
+    let mut start = None;
+    let mut end = None;
+    let tiles = Grid::parse(input, "a height (`a` to `z`), `S` or `E`", |p, c| match c {
+        'S' => {
+            start = Some(p);
+            Some('a')
+        }
+        'E' => {
+            end = Some(p);
+            Some('z')
+        }
+        c if c.is_ascii_lowercase() => Some(c),
+        _ => None,
+    })?;
+    let end_of_input = &input[input.len()..];
+    let start = start.ok_or_else(|| ParseError::at(input, end_of_input, "a start position `S`"))?;
+    let end =
+        end.ok_or_else(|| ParseError::at(input, end_of_input, "a best signal position `E`"))?;
+
+    Ok(Map { tiles, start, end })
+}
+
+// Draws the map with the path highlighted, for the renderer in `main.rs`.
+//
+// `&[...]` is short for `&Vec<...>`.
+// This was a suggestion from Clippy (run `cargo clippy`).
+pub fn path_frame(map: &Map, path: &[Point]) -> Frame {
+    let mut frame = Frame::new();
+    for (p, &tile) in map.tiles.iter() {
+        let c = if p == map.start {
+            'S'
+        } else if p == map.end {
+            'E'
+        } else {
+            tile
+        };
+        let style = if path.contains(&p) {
+            Style::Highlight
+        } else {
+            Style::Plain
+        };
+        frame.push(c, style);
+        if p.x == map.tiles.width() - 1 {
+            frame.newline();
+        }
+    }
+    frame
+}
+
+// The height map from green valleys to snowy peaks, with the path in red and the start and the
+// best signal in blue.
+pub fn path_image(map: &Map, path: &[Point]) -> Image {
+    let (low, high) = (Rgb(30, 90, 40), Rgb(240, 240, 240));
+    let width = map.tiles.width() as usize;
+    let height = map.tiles.height() as usize;
+    Image::from_fn(width, height, |x, y| {
+        let p = Point::new(x as i32, y as i32);
+        if p == map.start || p == map.end {
+            Rgb(40, 90, 230)
+        } else if path.contains(&p) {
+            Rgb(220, 40, 40)
+        } else {
+            let t = (map.tiles[p] as u8 - b'a') as f64 / 25.0;
+            low.mix(high, t)
+        }
+    })
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Map;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(map: &Self::Input) -> usize {
+        let path = find_shortest_path(map, map.end, map.start, 'E').unwrap();
+        path.len() - 1
+    }
+
+    fn part2(map: &Self::Input) -> usize {
+        let path = find_shortest_path(map, map.end, map.start, 'a').unwrap();
+        path.len() - 1
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day12::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day12::part1(&input), 31);
+    assert_eq!(Day12::part2(&input), 29);
+}
+
+// The example with the path of part 1, saved with
+// `aoc image 12 day12/example.ppm --input day12/example.txt --part 1`.
+#[test]
+fn test_image() {
+    let map = Day12::parse(include_str!("../example.txt")).unwrap();
+    let path = find_shortest_path(&map, map.end, map.start, 'E').unwrap();
+    let expected = Image::from_ppm(include_bytes!("../example.ppm")).unwrap();
+    assert_eq!(path_image(&map, &path), expected);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day12::parse(INPUT).unwrap();
+    assert_eq!(Day12::part1(&input), 534);
+    assert_eq!(Day12::part2(&input), 525);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day12>(INPUT);
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random maps that always have a path: the heights go from `a` in the first column to `z` in the
+// last one, by at most one per column. Every cell is at the height of its column or one below, so
+// we can go anywhere within a column, and from the highest cells to any cell of the next column.
+#[cfg(test)]
+fn arb_map() -> impl Strategy<Value = String> {
+    use proptest::collection::vec;
+    (0..10usize, 1..6usize)
+        .prop_flat_map(|(flat, height)| {
+            let mut steps = vec![true; 25];
+            steps.extend(vec![false; flat]);
+            let width = 26 + flat;
+            (
+                Just(steps).prop_shuffle(),
+                vec(vec(any::<bool>(), height), width),
+                0..height,
+                0..height,
+            )
+        })
+        .prop_map(|(steps, lower, start, end)| {
+            let height = lower[0].len();
+            let mut columns = vec![];
+            let mut level = 0;
+            for (x, lower) in lower.iter().enumerate() {
+                if x > 0 && steps[x - 1] {
+                    level += 1;
+                }
+                let mut column: Vec<char> = lower
+                    .iter()
+                    .map(|&lower| {
+                        let cell = if lower && level > 0 { level - 1 } else { level };
+                        (b'a' + cell) as char
+                    })
+                    .collect();
+                // At least one cell at the height of the column.
+                column[x % height] = (b'a' + level) as char;
+                columns.push(column);
+            }
+            columns[0][start] = 'S';
+            columns.last_mut().unwrap()[end] = 'E';
+            let mut map = String::new();
+            for y in 0..height {
+                map.extend(columns.iter().map(|column| column[y]));
+                map.push('\n');
+            }
+            map
+        })
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties(input in arb_map()) {
+        let map = Day12::parse(&input).unwrap();
+        let part1 = Day12::part1(&map);
+        // The start is one of the `a`s, so the best `a` can only be closer.
+        prop_assert!(Day12::part2(&map) <= part1);
+        let distance = map.start.manhattan(map.end) as usize;
+        prop_assert!(part1 >= distance);
+    }
+}
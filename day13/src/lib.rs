@@ -0,0 +1,282 @@
+use std::cmp::Ordering;
+
+use common::{ParseError, Solution};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+#[derive(Debug)]
+pub struct Pair {
+    left: String,
+    right: String,
+}
+
+fn read_input(input: &str) -> Result<Vec<Pair>, ParseError> {
+    let mut pairs = vec![];
+    // Without the filter(), an empty input would be one empty (and broken) pair.
+    for chunk in input.trim().split("\n\n").filter(|chunk| !chunk.is_empty()) {
+        let lines = chunk
+            .split_once('\n')
+            .ok_or_else(|| ParseError::at(input, chunk, "a pair of packets on two lines"))?;
+        check_packet(input, lines.0)?;
+        check_packet(input, lines.1)?;
+        pairs.push(Pair {
+            left: lines.0.to_string(),
+            right: lines.1.to_string(),
+        });
+    }
+    Ok(pairs)
+}
+
+// What can come next in a packet.
+#[derive(Clone, Copy)]
+enum Expect {
+    // After a `,`.
+    Item,
+    // After a `[`.
+    ItemOrClose,
+    // After a number or a `]`.
+    CommaOrClose,
+}
+
+// `compare_lists` only works on well-formed packets, so we check that a packet is a list, only
+// contains numbers (that fit into a u32) and other lists separated by commas, and that all the
+// brackets match up. Checking which character comes after which one is enough for that.
+fn check_packet(input: &str, packet: &str) -> Result<(), ParseError> {
+    if !packet.starts_with('[') {
+        return Err(ParseError::at(input, packet, "a packet starting with `[`"));
+    }
+    let mut depth = 0;
+    let mut expect = Expect::Item;
+    let mut chars = packet.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let found = &packet[i..i + c.len_utf8()];
+        match (c, expect) {
+            ('[', Expect::Item | Expect::ItemOrClose) => {
+                depth += 1;
+                expect = Expect::ItemOrClose;
+            }
+            (']', Expect::ItemOrClose | Expect::CommaOrClose) => {
+                depth -= 1;
+                if depth == 0 && i + 1 < packet.len() {
+                    return Err(ParseError::at(input, &packet[i + 1..], "the end of the packet"));
+                }
+                expect = Expect::CommaOrClose;
+            }
+            ('0'..='9', Expect::Item | Expect::ItemOrClose) => {
+                // next_if() only takes the next character if it is a digit too.
+                let mut end = i + 1;
+                while let Some((j, _)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    end = j + 1;
+                }
+                let number = &packet[i..end];
+                if number.parse::<u32>().is_err() {
+                    return Err(ParseError::at(input, number, "a number that fits into 32 bits"));
+                }
+                expect = Expect::CommaOrClose;
+            }
+            (',', Expect::CommaOrClose) => expect = Expect::Item,
+            (_, Expect::Item) => return Err(ParseError::at(input, found, "a number or `[`")),
+            (_, Expect::ItemOrClose) => {
+                return Err(ParseError::at(input, found, "a number, `[` or `]`"))
+            }
+            (_, Expect::CommaOrClose) => return Err(ParseError::at(input, found, "`,` or `]`")),
+        }
+    }
+    if depth > 0 {
+        return Err(ParseError::at(input, &packet[packet.len()..], "a closing `]`"));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_packet() {
+    for packet in ["[]", "[1]", "[[],[1,[2]],10]", "[4294967295]"] {
+        assert!(check_packet(packet, packet).is_ok(), "{packet}");
+    }
+    for packet in ["[1,]", "[,1]", "[1,,2]", "[1[2]]", "[[]1]", "[4294967296]", "[1]]", "[[1]"] {
+        assert!(check_packet(packet, packet).is_err(), "{packet}");
+    }
+}
+
+fn list_items(list: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut start = 0;
+    let mut depth = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            '[' => {
+                depth += 1;
+                if depth == 1 {
+                    start = i + 1
+                }
+            }
+            ']' => {
+                if depth == 1 && start < i {
+                    result.push(&list[start..i]);
+                }
+                depth -= 1;
+            }
+            ',' if depth == 1 => {
+                result.push(&list[start..i]);
+                start = i + 1; // Skip comma
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+#[test]
+fn test_list_items() {
+    assert_eq!(list_items("[]"), Vec::<String>::new());
+    assert_eq!(list_items("[1]"), vec!["1"]);
+    assert_eq!(list_items("[1,2]"), vec!["1", "2"]);
+    assert_eq!(list_items("[1,[2]]"), vec!["1", "[2]"]);
+    assert_eq!(list_items("[[1],2]"), vec!["[1]", "2"]);
+    assert_eq!(list_items("[[]]"), vec!["[]"]);
+}
+
+fn compare_lists(left: &str, right: &str) -> Ordering {
+    let left_list = list_items(left);
+    let right_list = list_items(right);
+    let mut result: Ordering;
+
+    for (left_item, right_item) in left_list.iter().zip(right_list.iter()) {
+        if left_item.starts_with('[') && right_item.starts_with('[') {
+            result = compare_lists(left_item, right_item);
+        } else if left_item.starts_with('[') {
+            result = compare_lists(left_item, format!("[{right_item}]").as_str());
+        } else if right_item.starts_with('[') {
+            result = compare_lists(format!("[{left_item}]").as_str(), right_item);
+        } else {
+            result = left_item
+                .parse::<u32>()
+                .unwrap()
+                .cmp(&right_item.parse::<u32>().unwrap());
+        }
+        if result != Ordering::Equal {
+            return result;
+        }
+    }
+
+    left_list.len().cmp(&right_list.len())
+}
+
+#[test]
+fn test_compare_lists() {
+    assert_eq!(compare_lists("[1,1,3,1,1]", "[1,1,5,1,1]"), Ordering::Less);
+    assert_eq!(compare_lists("[[1],[2,3,4]]", "[[1],4]"), Ordering::Less);
+    assert_eq!(compare_lists("[9]", "[[8,7,6]]"), Ordering::Greater);
+    assert_eq!(
+        compare_lists("[[4,4],4,4]", "[[4,4],4,4,4]"),
+        Ordering::Less
+    );
+    assert_eq!(compare_lists("[7,7,7,7]", "[7,7,7]"), Ordering::Greater);
+    assert_eq!(compare_lists("[]", "[3]"), Ordering::Less);
+    assert_eq!(compare_lists("[[[]]]", "[[]]"), Ordering::Greater);
+    assert_eq!(
+        compare_lists("[1,[2,[3,[4,[5,6,7]]]],8,9]", "[1,[2,[3,[4,[5,6,0]]]],8,9]"),
+        Ordering::Greater
+    );
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Pair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(pairs: &Self::Input) -> usize {
+        pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| compare_lists(&p.left, &p.right) == Ordering::Less)
+            .map(|(i, _)| i + 1)
+            .sum()
+    }
+
+    // Part 2 doesn't care about the pairs, only about the packets.
+    fn part2(pairs: &Self::Input) -> usize {
+        let mut packets: Vec<&str> = pairs
+            .iter()
+            .flat_map(|p| [p.left.as_str(), p.right.as_str()])
+            .collect();
+        packets.push("[[2]]");
+        packets.push("[[6]]");
+        packets.sort_by(|a, b| compare_lists(a, b));
+        let pos1 = packets.iter().position(|x| *x == "[[2]]").unwrap() + 1;
+        let pos2 = packets.iter().position(|x| *x == "[[6]]").unwrap() + 1;
+        pos1 * pos2
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day13::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day13::part1(&input), 13);
+    assert_eq!(Day13::part2(&input), 140);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day13::parse(INPUT).unwrap();
+    assert_eq!(Day13::part1(&input), 6235);
+    assert_eq!(Day13::part2(&input), 22866);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day13>(INPUT);
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random packets, as their text. Lists of up to 4 items, nested up to 4 levels deep.
+#[cfg(test)]
+fn arb_packet() -> impl Strategy<Value = String> {
+    let leaf = (0..12u32).prop_map(|n| n.to_string());
+    let item = leaf.prop_recursive(4, 32, 4, |inner| {
+        proptest::collection::vec(inner, 0..4).prop_map(|items| format!("[{}]", items.join(",")))
+    });
+    proptest::collection::vec(item, 0..4).prop_map(|items| format!("[{}]", items.join(",")))
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_compare_properties(a in arb_packet(), b in arb_packet()) {
+        prop_assert!(check_packet(&a, &a).is_ok());
+        prop_assert_eq!(compare_lists(&a, &a), Ordering::Equal);
+        prop_assert_eq!(compare_lists(&a, &b), compare_lists(&b, &a).reverse());
+    }
+
+    #[test]
+    fn test_properties(packets in proptest::collection::vec(arb_packet(), 0..20)) {
+        let input: String = packets
+            .chunks_exact(2)
+            .map(|pair| format!("{}\n{}\n\n", pair[0], pair[1]))
+            .collect();
+        let pairs = Day13::parse(&input).unwrap();
+        let n = pairs.len();
+        prop_assert!(Day13::part1(&pairs) <= n * (n + 1) / 2);
+        // [[2]] comes before [[6]], so the lowest possible answer is 1 * 2.
+        let part2 = Day13::part2(&pairs);
+        prop_assert!(part2 >= 2 && part2 <= (2 * n + 1) * (2 * n + 2));
+    }
+
+    // Whatever the input is, we either get an error or an answer, but never a panic.
+    #[test]
+    fn test_any_input(input in "([\\[\\],0-9]{0,12}\n[\\[\\],0-9]{0,12}\n\n){1,3}") {
+        if let Ok(pairs) = Day13::parse(&input) {
+            Day13::part1(&pairs);
+            Day13::part2(&pairs);
+        }
+    }
+}
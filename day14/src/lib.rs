@@ -0,0 +1,236 @@
+use std::str::FromStr;
+
+use common::{ParseError, Solution};
+use grid::{Point, SparseGrid};
+use viz::{Image, Rgb};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    source: Point,
+    cells: SparseGrid<char>,
+    max_y: i32,
+}
+
+impl Map {
+    // Sand falls straight down if it can, otherwise diagonally to the left or to the right.
+    fn move_grain(&self, p: &mut Point) -> bool {
+        let moves = [
+            Point::DOWN,
+            Point::DOWN + Point::LEFT,
+            Point::DOWN + Point::RIGHT,
+        ];
+        match moves.into_iter().find(|&d| !self.cells.contains(*p + d)) {
+            Some(d) => {
+                *p += d;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn simulate_sand_part1(&mut self) -> Option<Point> {
+        let mut p: Point = self.source;
+        // Like in part 2, or a rock on the source would get sand on it forever.
+        if self.cells.contains(p) {
+            return None;
+        }
+        while p.y < self.max_y {
+            if !self.move_grain(&mut p) {
+                self.cells.insert(p, 'o');
+                return Some(p);
+            }
+        }
+        None
+    }
+
+    fn simulate_sand_part2(&mut self) -> Option<Point> {
+        let mut p: Point = self.source;
+        if self.cells.contains(p) {
+            return None;
+        }
+        while p.y < self.max_y + 1 {
+            if !self.move_grain(&mut p) {
+                self.cells.insert(p, 'o');
+                return Some(p);
+            }
+        }
+        self.cells.insert(p, 'o');
+        Some(p)
+    }
+
+    // The rocks and the sand, and the source of the sand in red. With `floor`, the floor of part 2
+    // is drawn as well. The image only covers the parts of the cave that have something in them.
+    pub fn image(&self, floor: bool) -> Image {
+        let (mut min, mut max) = self.cells.bounds().unwrap_or((self.source, self.source));
+        min = Point::new(min.x.min(self.source.x), min.y.min(self.source.y));
+        max = Point::new(max.x.max(self.source.x), max.y.max(self.source.y));
+        if floor {
+            max.y = max.y.max(self.max_y + 2);
+        }
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        Image::from_fn(width, height, |x, y| {
+            let p = min + Point::new(x as i32, y as i32);
+            match self.cells.get(p) {
+                _ if p == self.source => Rgb(230, 50, 50),
+                Some('#') => Rgb(110, 100, 90),
+                Some(_) => Rgb(240, 200, 100),
+                None if floor && p.y == self.max_y + 2 => Rgb(110, 100, 90),
+                None => Rgb(20, 20, 30),
+            }
+        })
+    }
+}
+
+// Lets sand fall until it doesn't come to rest anymore (part 1) or until it blocks the source
+// (part 2). Returns the cave full of sand, and the number of grains that came to rest.
+pub fn pour_sand(map: &Map, part: u8) -> (Map, u32) {
+    let mut map = map.clone();
+    let mut result = 0;
+    let simulate = match part {
+        1 => Map::simulate_sand_part1,
+        _ => Map::simulate_sand_part2,
+    };
+    while simulate(&mut map).is_some() {
+        result += 1;
+    }
+    (map, result)
+}
+
+fn read_input(input: &str) -> Result<Map, ParseError> {
+    let mut map = Map {
+        source: Point::new(500, 0),
+        cells: SparseGrid::new(),
+        max_y: 0,
+    };
+    for line in input.lines() {
+        let mut points = vec![];
+        for coords in line.split(" -> ") {
+            let point = Point::from_str(coords)
+                .map_err(|_| ParseError::at(input, coords, "a point like `498,4`"))?;
+            // Rock paths only go in straight lines.
+            if let Some(prev) = points.last() {
+                let prev: &Point = prev;
+                if prev.x != point.x && prev.y != point.y {
+                    return Err(ParseError::at(
+                        input,
+                        coords,
+                        format!("a point in a straight line from {},{}", prev.x, prev.y),
+                    ));
+                }
+            }
+            points.push(point);
+        }
+        for pair in points.windows(2) {
+            // Walk from one point to the next, one step at a time.
+            let (mut p, end) = (pair[0], pair[1]);
+            let step = (end - p).signum();
+            map.cells.insert(p, '#');
+            while p != end {
+                p += step;
+                map.cells.insert(p, '#');
+            }
+            map.max_y = map.max_y.max(pair[0].y).max(pair[1].y);
+        }
+    }
+    Ok(map)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Map;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(map: &Self::Input) -> u32 {
+        pour_sand(map, 1).1
+    }
+
+    fn part2(map: &Self::Input) -> u32 {
+        pour_sand(map, 2).1
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day14::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day14::part1(&input), 24);
+    assert_eq!(Day14::part2(&input), 93);
+}
+
+// The example full of sand after part 2, saved with
+// `aoc image 14 day14/example.ppm --input day14/example.txt`.
+#[test]
+fn test_image() {
+    let map = Day14::parse(include_str!("../example.txt")).unwrap();
+    let (map, _) = pour_sand(&map, 2);
+    let expected = Image::from_ppm(include_bytes!("../example.ppm")).unwrap();
+    assert_eq!(map.image(true), expected);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day14::parse(INPUT).unwrap();
+    assert_eq!(Day14::part1(&input), 799);
+    assert_eq!(Day14::part2(&input), 29076);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day14>(INPUT);
+}
+
+#[test]
+fn test_blocked_source() {
+    let map = Day14::parse("499,0 -> 501,0\n").unwrap();
+    assert_eq!(Day14::part1(&map), 0);
+    assert_eq!(Day14::part2(&map), 0);
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random rock paths around the source, that turn at every point. Some of them go through the
+// source.
+#[cfg(test)]
+fn arb_scan() -> impl Strategy<Value = String> {
+    use proptest::collection::vec;
+    let path = (490..510i32, 0..15i32, vec((any::<bool>(), -5..=5i32), 1..4)).prop_map(
+        |(mut x, mut y, moves)| {
+            let mut points = vec![format!("{x},{y}")];
+            for (horizontal, length) in moves {
+                if horizontal {
+                    x += length;
+                } else {
+                    y = (y + length).max(0);
+                }
+                points.push(format!("{x},{y}"));
+            }
+            points.join(" -> ") + "\n"
+        },
+    );
+    vec(path, 0..6).prop_map(|paths| paths.concat())
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties(scan in arb_scan()) {
+        let map = Day14::parse(&scan).unwrap();
+        let part1 = Day14::part1(&map);
+        let part2 = Day14::part2(&map);
+        // The floor only catches more sand, and the sand can't go further than a triangle below
+        // the source.
+        let height = map.max_y as u32 + 2;
+        prop_assert!(part1 <= part2);
+        prop_assert!(part2 <= height * height);
+    }
+}
@@ -0,0 +1,124 @@
+// The puzzle never says what the second column of the guide means. Part 1 guesses that X, Y and Z
+// are rock, paper and scissors, and part 2 that they're a loss, a draw and a win. But they could
+// be any of the 6 orders of the moves or of the outcomes, so here we try all 12 interpretations
+// and see which one the guide is best (and worst) for.
+
+use std::fmt;
+
+use crate::scoring::Scoring;
+use crate::{get_move, play, Choice, Outcome};
+
+// What X, Y and Z stand for, in that order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Meaning {
+    Choices([Choice; 3]),
+    Outcomes([Outcome; 3]),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interpretation {
+    pub meaning: Meaning,
+    pub score: u64,
+}
+
+// The orders of three things, as indices.
+const PERMUTATIONS: [[usize; 3]; 6] =
+    [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+const OUTCOMES: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+impl Meaning {
+    // The two interpretations of the puzzle.
+    pub const PART1: Meaning = Meaning::Choices(Choice::ALL);
+    pub const PART2: Meaning = Meaning::Outcomes(OUTCOMES);
+
+    pub fn all() -> Vec<Meaning> {
+        let choices = PERMUTATIONS.map(|p| Meaning::Choices(p.map(|i| Choice::ALL[i])));
+        let outcomes = PERMUTATIONS.map(|p| Meaning::Outcomes(p.map(|i| OUTCOMES[i])));
+        choices.into_iter().chain(outcomes).collect()
+    }
+
+    // Our choice and the outcome of a round. The guide only has X, Y and Z in the second column
+    // (`parse()` checks that).
+    fn round(&self, their_choice: &Choice, second: char) -> (Choice, Outcome) {
+        let index = (second as u8 - b'X') as usize;
+        match self {
+            Meaning::Choices(choices) => (choices[index], play(their_choice, &choices[index])),
+            Meaning::Outcomes(outcomes) => {
+                (get_move(their_choice, &outcomes[index]), outcomes[index])
+            }
+        }
+    }
+
+    pub fn score(&self, rounds: &[(Choice, char)], scoring: &Scoring) -> u64 {
+        scoring.total(rounds.iter().map(|(their_choice, second)| self.round(their_choice, *second)))
+    }
+}
+
+// All 12 interpretations of the guide, the highest score first. Those with the same score are in
+// the order of `Meaning::all()`, so the moves come before the outcomes.
+pub fn interpretations(rounds: &[(Choice, char)], scoring: &Scoring) -> Vec<Interpretation> {
+    let mut all: Vec<Interpretation> = Meaning::all()
+        .into_iter()
+        .map(|meaning| Interpretation { meaning, score: meaning.score(rounds, scoring) })
+        .collect();
+    // A stable sort keeps the order of ties.
+    all.sort_by_key(|i| std::cmp::Reverse(i.score));
+    all
+}
+
+// Prints e.g. `X=rock Y=paper Z=scissors`.
+impl fmt::Display for Meaning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = match self {
+            Meaning::Choices(choices) => choices.iter().map(|c| format!("{c:?}")).collect(),
+            Meaning::Outcomes(outcomes) => outcomes.iter().map(|o| format!("{o:?}")).collect(),
+        };
+        let columns: Vec<String> = ['X', 'Y', 'Z']
+            .iter()
+            .zip(names)
+            .map(|(symbol, name)| format!("{symbol}={}", name.to_lowercase()))
+            .collect();
+        write!(f, "{}", columns.join(" "))
+    }
+}
+
+#[test]
+fn test_interpretations() {
+    use common::Solution;
+    let rounds = crate::Day2::parse(include_str!("../example.txt")).unwrap();
+    let all = interpretations(&rounds, &Scoring::default());
+    assert_eq!(all.len(), 12);
+    let score = |meaning| all.iter().find(|i| i.meaning == meaning).unwrap().score;
+    assert_eq!(score(Meaning::PART1), 15);
+    assert_eq!(score(Meaning::PART2), 12);
+    // Winning every round: paper against rock (2 + 6), scissors against paper (3 + 6) and rock
+    // against scissors (1 + 6).
+    let best = Meaning::Choices([Choice::Scissors, Choice::Paper, Choice::Rock]);
+    assert_eq!(all[0], Interpretation { meaning: best, score: 24 });
+    // And losing every round, with the same moves in a different order.
+    let worst = Meaning::Choices([Choice::Rock, Choice::Scissors, Choice::Paper]);
+    assert_eq!(all[11], Interpretation { meaning: worst, score: 6 });
+    assert!(all.windows(2).all(|w| w[0].score >= w[1].score));
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(Meaning::PART1.to_string(), "X=rock Y=paper Z=scissors");
+    assert_eq!(Meaning::PART2.to_string(), "X=loss Y=draw Z=win");
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    // The puzzle's interpretations are two of the twelve, so they're between the best and the
+    // worst one.
+    #[test]
+    fn test_best_and_worst(guide in crate::arb_guide()) {
+        use common::Solution;
+        let rounds = crate::Day2::parse(&guide).unwrap();
+        let all = interpretations(&rounds, &Scoring::default());
+        for score in [crate::Day2::part1(&rounds), crate::Day2::part2(&rounds)] {
+            proptest::prop_assert!(all[0].score >= score && score >= all[11].score);
+        }
+    }
+}
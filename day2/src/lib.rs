@@ -0,0 +1,247 @@
+use std::io::BufRead;
+
+use common::{LineReader, ParseError, Solution, StreamError, StreamSolution};
+
+use rules::Game;
+use scoring::{Scoring, Tally};
+
+pub mod interpret;
+pub mod rules;
+pub mod scoring;
+pub mod tournament;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+// The scores of the moves and outcomes used to be the values of these enums. They're in
+// `scoring.rs` now, so they can be changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Choice {
+    // In the order of the moves in the Rock-Paper-Scissors game (see `rules.rs`).
+    const ALL: [Choice; 3] = [Choice::Rock, Choice::Paper, Choice::Scissors];
+
+    fn index(self) -> usize {
+        match self {
+            Choice::Rock => 0,
+            Choice::Paper => 1,
+            Choice::Scissors => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    fn index(self) -> usize {
+        match self {
+            Outcome::Loss => 0,
+            Outcome::Draw => 1,
+            Outcome::Win => 2,
+        }
+    }
+
+    // The outcome for the other player.
+    fn reverse(self) -> Outcome {
+        match self {
+            Outcome::Loss => Outcome::Win,
+            Outcome::Draw => Outcome::Draw,
+            Outcome::Win => Outcome::Loss,
+        }
+    }
+}
+
+fn read_choice(c: char) -> Option<Choice> {
+    match c {
+        'A' | 'X' => Some(Choice::Rock),
+        'B' | 'Y' => Some(Choice::Paper),
+        'C' | 'Z' => Some(Choice::Scissors),
+        _ => None,
+    }
+}
+
+// Who wins used to be a `match` with a line for every pair of moves. Now it comes from the rules
+// of the game, like for any other game.
+fn play(their_choice: &Choice, our_choice: &Choice) -> Outcome {
+    Game::rock_paper_scissors().play(their_choice.index(), our_choice.index())
+}
+
+fn read_outcome(c: char) -> Option<Outcome> {
+    match c {
+        'X' => Some(Outcome::Loss),
+        'Y' => Some(Outcome::Draw),
+        'Z' => Some(Outcome::Win),
+        _ => None,
+    }
+}
+
+fn get_move(their_choice: &Choice, outcome: &Outcome) -> Choice {
+    Choice::ALL[Game::rock_paper_scissors().get_move(their_choice.index(), *outcome)]
+}
+
+// Parses a single character, like the "A" or "X" in "A X".
+fn read_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    let c = chars.next()?;
+    match chars.next() {
+        None => Some(c),
+        Some(_) => None,
+    }
+}
+
+// Parses one line of the strategy guide. `content` is the whole input, for the error positions.
+fn parse_round(content: &str, line: &str) -> Result<(Choice, char), ParseError> {
+    let (first, second) = line
+        .split_once(' ')
+        .ok_or_else(|| ParseError::at(content, line, "two columns like `A Y`"))?;
+    let their_choice = read_char(first)
+        .and_then(read_choice)
+        .ok_or_else(|| ParseError::at(content, first, "one of A, B or C"))?;
+    // Both parts need the second column to be one of X, Y or Z.
+    let second = read_char(second)
+        .filter(|c| read_outcome(*c).is_some())
+        .ok_or_else(|| ParseError::at(content, second, "one of X, Y or Z"))?;
+    Ok((their_choice, second))
+}
+
+// Our choice and the outcome of a round, which is what we need for the score, when the second
+// column is our choice (part 1)...
+fn round1(their_choice: &Choice, second: char) -> (Choice, Outcome) {
+    let our_choice = read_choice(second).unwrap();
+    (our_choice, play(their_choice, &our_choice))
+}
+
+// ... and when it's the outcome (part 2).
+fn round2(their_choice: &Choice, second: char) -> (Choice, Outcome) {
+    let outcome = read_outcome(second).unwrap();
+    (get_move(their_choice, &outcome), outcome)
+}
+
+// Both parts with a different scoring than the puzzle's.
+pub fn part1_with(rounds: &[(Choice, char)], scoring: &Scoring) -> u64 {
+    scoring.total(rounds.iter().map(|(their_choice, second)| round1(their_choice, *second)))
+}
+
+pub fn part2_with(rounds: &[(Choice, char)], scoring: &Scoring) -> u64 {
+    scoring.total(rounds.iter().map(|(their_choice, second)| round2(their_choice, *second)))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    // What the second column means depends on the part, so we keep it as a character.
+    type Input = Vec<(Choice, char)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse_normalized(content: &str) -> Result<Self::Input, ParseError> {
+        content.lines().map(|line| parse_round(content, line)).collect()
+    }
+
+    fn part1(rounds: &Self::Input) -> u64 {
+        part1_with(rounds, &Scoring::default())
+    }
+
+    fn part2(rounds: &Self::Input) -> u64 {
+        part2_with(rounds, &Scoring::default())
+    }
+}
+
+// Every round counts for both parts, so we can add up both scores while reading the guide.
+impl StreamSolution for Day2 {
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(u64, u64), StreamError> {
+        let mut lines = LineReader::new(reader);
+        let scoring = Scoring::default();
+        let mut scores = (Tally::new(&scoring), Tally::new(&scoring));
+        while let Some((number, line)) = lines.next_line()? {
+            let (their_choice, second) =
+                parse_round(line, line).map_err(|err| err.on_line(number))?;
+            let (our_choice, outcome) = round1(&their_choice, second);
+            scores.0.add(scoring.choice(our_choice), outcome);
+            let (our_choice, outcome) = round2(&their_choice, second);
+            scores.1.add(scoring.choice(our_choice), outcome);
+        }
+        Ok((scores.0.total, scores.1.total))
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day2::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day2::part1(&input), 15);
+    assert_eq!(Day2::part2(&input), 12);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day2::parse(INPUT).unwrap();
+    assert_eq!(Day2::part1(&input), 10624);
+    assert_eq!(Day2::part2(&input), 14060);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day2>(INPUT);
+}
+
+// Reading the input bit by bit gives the same answers as parsing all of it first.
+#[test]
+fn test_stream() {
+    for input in [include_str!("../example.txt"), INPUT] {
+        let parsed = Day2::parse(input).unwrap();
+        let expected = (Day2::part1(&parsed), Day2::part2(&parsed));
+        assert_eq!(Day2::solve_stream(input.as_bytes()).unwrap(), expected);
+    }
+    match Day2::solve_stream("A Y\nB Q\n".as_bytes()) {
+        Err(StreamError::Parse(err)) => assert_eq!((err.line, err.column), (2, 3)),
+        _ => panic!("expected a parse error"),
+    }
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random strategy guides, with any of the 9 possible rounds.
+#[cfg(test)]
+fn arb_guide() -> impl Strategy<Value = String> {
+    let round = (0..3u8, 0..3u8)
+        .prop_map(|(a, b)| format!("{} {}\n", (b'A' + a) as char, (b'X' + b) as char));
+    proptest::collection::vec(round, 0..50).prop_map(|rounds| rounds.concat())
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties(guide in arb_guide()) {
+        let rounds = Day2::parse(&guide).unwrap();
+        let scores = (Day2::part1(&rounds), Day2::part2(&rounds));
+        prop_assert_eq!(Day2::solve_stream(guide.as_bytes()).unwrap(), scores);
+        let n = rounds.len() as u64;
+        // Every round is worth between 1 (rock and a loss) and 9 (scissors and a win) points.
+        for score in [Day2::part1(&rounds), Day2::part2(&rounds)] {
+            prop_assert!(score >= n && score <= 9 * n);
+        }
+    }
+
+    // The move we choose for an outcome really leads to that outcome.
+    #[test]
+    fn test_get_move(
+        their_choice in proptest::char::range('A', 'C'),
+        outcome in proptest::char::range('X', 'Z'),
+    ) {
+        let their_choice = read_choice(their_choice).unwrap();
+        let outcome = read_outcome(outcome).unwrap();
+        let our_choice = get_move(&their_choice, &outcome);
+        prop_assert_eq!(play(&their_choice, &our_choice), outcome);
+    }
+}
@@ -1,82 +1,188 @@
-use std::fs;
+// Prints both answers like the other days. With `--game <name or file>`, the strategy guide is for
+// a different game: one of the presets (rps, rpsls or rps7) or a definition file like the ones in
+// `games/` (see `rules.rs`):
+//
+//     cargo run -p day2 -- --game rpsls guide.txt
+//
+// `--interpretations` shows the score of the guide for every meaning of the second column,
+// instead of just the two of the puzzle (see `interpret.rs`). It only knows the puzzle's game.
+//
+// `--tournament <rounds>` lets strategies play against each other, starting with the two columns
+// of the guide (see `tournament.rs`). `--seed <n>` plays a different tournament, and `--log`
+// prints every round. The strategies only play the puzzle's game.
+//
+// All of them score the rounds like the puzzle, unless `--scoring` says otherwise, e.g.
+// `--scoring win=10,streak=3:4` (see `scoring.rs`).
 
-#[derive(Debug, Clone, Copy)]
-enum Choice {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
-}
+use std::env;
+use std::fs;
+use std::process;
 
-#[derive(Debug)]
-enum Outcome {
-    Loss = 0,
-    Draw = 3,
-    Win = 6,
-}
+use day2::interpret::{interpretations, Meaning};
+use day2::rules::Game;
+use day2::scoring::Scoring;
+use day2::tournament::{self, Fixed, Frequency, Markov, Random, Strategy, WinStayLoseShift};
+use day2::Day2;
 
-fn read_choice(s: &str) -> Option<Choice> {
-    match s {
-        "A" | "X" => Some(Choice::Rock),
-        "B" | "Y" => Some(Choice::Paper),
-        "C" | "Z" => Some(Choice::Scissors),
-        _ => None,
+fn main() {
+    let mut args = env::args().skip(1);
+    let mut game = None;
+    let mut interpret = false;
+    let mut rounds = None;
+    let mut seed = 2022;
+    let mut log = false;
+    let mut scoring = Scoring::default();
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--game" => match args.next() {
+                Some(name) => game = Some(name),
+                None => {
+                    eprintln!("error: --game needs the name of a preset or a definition file");
+                    process::exit(2);
+                }
+            },
+            "--interpretations" => interpret = true,
+            "--tournament" => rounds = Some(number(&arg, args.next()) as usize),
+            "--seed" => seed = number(&arg, args.next()),
+            "--log" => log = true,
+            "--scoring" => {
+                let value = args.next().unwrap_or_default();
+                scoring = value.parse().unwrap_or_else(|err| {
+                    eprintln!("error: {err}");
+                    process::exit(2);
+                });
+            }
+            _ => path = Some(arg),
+        }
     }
-}
-
+    // The interpretations are the ones of the puzzle's game, X, Y and Z as rock, paper and
+    // scissors or as the outcomes.
+    if interpret {
+        if game.is_some() {
+            eprintln!("error: --interpretations only works with the puzzle's game, not --game");
+            process::exit(2);
+        }
+        print_interpretations(path.as_deref(), &scoring);
+        return;
+    }
+    // The strategies only know rock, paper and scissors, too.
+    if let Some(rounds) = rounds {
+        if game.is_some() {
+            eprintln!("error: --tournament only works with the puzzle's game, not --game");
+            process::exit(2);
+        }
+        play_tournament(path.as_deref(), rounds, seed, log, &scoring);
+        return;
+    }
+    let Some(game) = game else {
+        let input = common::input::load_or_exit(path.as_deref(), day2::INPUT);
+        let rounds = common::parse_or_exit::<Day2>(&input);
+        println!("part 1: {}", day2::part1_with(&rounds, &scoring));
+        println!("part 2: {}", day2::part2_with(&rounds, &scoring));
+        return;
+    };
+    let game = load_game(&game);
+    let input = common::input::load_or_exit(path.as_deref(), day2::INPUT);
+    let input = common::input::normalize(&input);
 
-fn play(their_choice: &Choice, our_choice: &Choice) -> Outcome {
-    match (their_choice, our_choice) {
-        (Choice::Rock, Choice::Paper) => Outcome::Win,
-        (Choice::Rock, Choice::Scissors) => Outcome::Loss,
-        (Choice::Paper, Choice::Rock) => Outcome::Loss,
-        (Choice::Paper, Choice::Scissors) => Outcome::Win,
-        (Choice::Scissors, Choice::Rock) => Outcome::Win,
-        (Choice::Scissors, Choice::Paper) => Outcome::Loss,
-        _ => Outcome::Draw
+    // The guide may only work for one of the parts, e.g. if the second column has our moves, but
+    // there are more of them than outcomes.
+    match game.parse_moves(&input) {
+        Ok(rounds) => println!("part 1: {}", game.score_moves(&rounds, &scoring)),
+        Err(err) => println!("part 1: error: {err}"),
+    }
+    match game.parse_outcomes(&input) {
+        Ok(rounds) => println!("part 2: {}", game.score_outcomes(&rounds, &scoring)),
+        Err(err) => println!("part 2: error: {err}"),
     }
 }
 
-
-fn read_outcome(s: &str) -> Option<Outcome> {
-    match s {
-        "X" => Some(Outcome::Loss),
-        "Y" => Some(Outcome::Draw),
-        "Z" => Some(Outcome::Win),
-        _ => None,
-    }
+fn number(flag: &str, value: Option<String>) -> u64 {
+    let value = value.unwrap_or_default();
+    value.parse().unwrap_or_else(|_| {
+        eprintln!("error: {flag} needs a number, not {value:?}");
+        process::exit(2);
+    })
 }
 
-fn get_move(their_choice: &Choice, outcome: &Outcome) -> Choice {
-    match (their_choice, outcome) {
-        (Choice::Rock, Outcome::Win) => Choice::Paper,
-        (Choice::Rock, Outcome::Loss) => Choice::Scissors,
-        (Choice::Paper, Outcome::Win) => Choice::Scissors,
-        (Choice::Paper, Outcome::Loss) => Choice::Rock,
-        (Choice::Scissors, Outcome::Win) => Choice::Rock,
-        (Choice::Scissors, Outcome::Loss) => Choice::Paper,
-        _ => *their_choice,
+fn print_interpretations(path: Option<&str>, scoring: &Scoring) {
+    let input = common::input::load_or_exit(path, day2::INPUT);
+    let rounds = common::parse_or_exit::<Day2>(&input);
+    let all = interpretations(&rounds, scoring);
+    for (rank, interpretation) in all.iter().enumerate() {
+        // The puzzle's interpretations can be the best or the worst one, too.
+        let mut notes = vec![];
+        if rank == 0 {
+            notes.push("best");
+        } else if rank == all.len() - 1 {
+            notes.push("worst");
+        }
+        match interpretation.meaning {
+            Meaning::PART1 => notes.push("part 1"),
+            Meaning::PART2 => notes.push("part 2"),
+            _ => {}
+        }
+        let note = if notes.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", notes.join(", "))
+        };
+        println!("{:>6}  {}{note}", interpretation.score, interpretation.meaning);
     }
 }
 
-fn main() {
-    let content = fs::read_to_string("input.txt").expect("Failed to read from input.txt");
-    let mut score = 0;
-    for line in content.lines() {
-        let (first, second) = line.split_once(' ').unwrap();
-        let their_choice = read_choice(first).unwrap();
-        let our_choice = read_choice(second).unwrap();
-        let outcome = play(&their_choice, &our_choice);
-        score += our_choice as u32 + outcome as u32;
+fn play_tournament(path: Option<&str>, rounds: usize, seed: u64, log: bool, scoring: &Scoring) {
+    let input = common::input::load_or_exit(path, day2::INPUT);
+    let guide = common::parse_or_exit::<Day2>(&input);
+    let mut players: Vec<Box<dyn Strategy>> = vec![
+        Box::new(Fixed::guide(&guide)),
+        Box::new(Fixed::opponent(&guide)),
+        Box::new(Random),
+        Box::new(Frequency::default()),
+        Box::new(WinStayLoseShift::default()),
+        Box::new(Markov::train(&guide)),
+    ];
+    let matches = tournament::round_robin(&mut players, rounds, seed);
+    if log {
+        for m in &matches {
+            println!("{} against {}:", players[m.a].name(), players[m.b].name());
+            for (i, round) in m.rounds.iter().enumerate() {
+                println!("{:>6}  {round}", i + 1);
+            }
+            let (a, b) = m.scores(scoring);
+            println!("points: {a} to {b}\n");
+        }
+    }
+    println!(
+        "{:<20} {:>7} {:>7} {:>7} {:>9} {:>9}",
+        "strategy", "wins", "draws", "losses", "win rate", "points"
+    );
+    let standings = tournament::standings(players.len(), &matches, scoring);
+    for (player, standing) in players.iter().zip(standings) {
+        println!(
+            "{:<20} {:>7} {:>7} {:>7} {:>8.1}% {:>9}",
+            player.name(),
+            standing.wins,
+            standing.draws,
+            standing.losses,
+            standing.win_rate() * 100.0,
+            standing.points
+        );
     }
-    println!("part 1: {}", score);
+}
 
-    let mut score = 0;
-    for line in content.lines() {
-        let (first, second) = line.split_once(' ').unwrap();
-        let their_choice = read_choice(first).unwrap();
-        let outcome = read_outcome(second).unwrap();
-        let our_choice = get_move(&their_choice, &outcome);
-        score += our_choice as u32 + outcome as u32;
+fn load_game(name: &str) -> Game {
+    if let Some(game) = Game::preset(name) {
+        return game;
     }
-    println!("part 2: {}", score);
+    let definition = fs::read_to_string(name).unwrap_or_else(|err| {
+        eprintln!("error: {name} is neither a preset nor a file we can read: {err}");
+        process::exit(1);
+    });
+    let definition = common::input::normalize(&definition);
+    Game::parse(&definition).unwrap_or_else(|err| {
+        eprintln!("error: failed to parse game {name}: {err}");
+        process::exit(1);
+    })
 }
@@ -0,0 +1,326 @@
+// The rules of cyclic games like Rock-Paper-Scissors, read from a definition file instead of
+// written down as `match`es: which moves there are, their symbols in the strategy guide, their
+// scores and which moves they beat (see `games/rps.txt` for the format). The puzzle's game is one
+// of the presets, Rock-Paper-Scissors-Lizard-Spock and RPS-7 are the others.
+//
+// A game needs an odd number of moves, where every move beats half of the others and loses to the
+// other half. Then no move is better than another one, and there's always a move to win, lose or
+// draw with.
+
+use std::sync::OnceLock;
+
+use common::ParseError;
+
+use crate::scoring::{Scoring, Tally, MAX_POINTS};
+use crate::{read_char, Outcome};
+
+// The games that come with day 2, by name.
+pub const PRESETS: [(&str, &str); 3] = [
+    ("rps", include_str!("../games/rps.txt")),
+    ("rpsls", include_str!("../games/rpsls.txt")),
+    ("rps7", include_str!("../games/rps7.txt")),
+];
+
+#[derive(Debug)]
+pub struct Move {
+    pub name: String,
+    // The symbols of the move in the first column of the guide (their move) and in the second
+    // column (our move, in part 1).
+    pub their: char,
+    pub our: char,
+    pub score: u32,
+}
+
+// Moves are referred to by their index in `moves`.
+#[derive(Debug)]
+pub struct Game {
+    pub moves: Vec<Move>,
+    // `beats[a][b]` is whether move `a` beats move `b`.
+    beats: Vec<Vec<bool>>,
+    // The symbols of a loss, a draw and a win in the second column (in part 2).
+    pub outcomes: [char; 3],
+}
+
+const OUTCOMES: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+impl Game {
+    pub fn preset(name: &str) -> Option<Game> {
+        let (_, definition) = PRESETS.iter().find(|(preset, _)| *preset == name)?;
+        // The presets are tested, so they always parse.
+        Some(Game::parse(definition).unwrap())
+    }
+
+    // The game of the puzzle. It's only parsed once, the first time we need it.
+    pub fn rock_paper_scissors() -> &'static Game {
+        static GAME: OnceLock<Game> = OnceLock::new();
+        GAME.get_or_init(|| Game::preset("rps").unwrap())
+    }
+
+    pub fn parse(definition: &str) -> Result<Game, ParseError> {
+        let mut moves: Vec<Move> = vec![];
+        // The names of the moves and the names of the moves they beat, as slices of the definition
+        // for the errors. We can only look the names up once we've seen all the moves.
+        let mut names: Vec<(&str, Vec<&str>)> = vec![];
+        let mut outcomes = ['X', 'Y', 'Z'];
+        let used = "a symbol that isn't used yet";
+        for line in definition.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields[..] {
+                [] => continue,
+                [first, ..] if first.starts_with('#') => continue,
+                ["outcomes", loss, draw, win] => {
+                    for (i, s) in [loss, draw, win].into_iter().enumerate() {
+                        outcomes[i] = symbol(definition, s)?;
+                        if outcomes[..i].contains(&outcomes[i]) {
+                            return Err(ParseError::at(definition, s, used));
+                        }
+                    }
+                }
+                [name, their, our, score, ref beats @ ..] if name != "outcomes" => {
+                    if names.iter().any(|(n, _)| *n == name) {
+                        return Err(ParseError::at(definition, name, "a name that isn't used yet"));
+                    }
+                    let their_symbol = symbol(definition, their)?;
+                    let our_symbol = symbol(definition, our)?;
+                    if moves.iter().any(|m| m.their == their_symbol) {
+                        return Err(ParseError::at(definition, their, used));
+                    }
+                    if moves.iter().any(|m| m.our == our_symbol) {
+                        return Err(ParseError::at(definition, our, used));
+                    }
+                    // The same limit as for `--scoring`, so the totals can't overflow.
+                    let points = score.parse().ok().filter(|&points| points <= MAX_POINTS);
+                    let expected = format!("a score up to {MAX_POINTS}");
+                    let points = points.ok_or_else(|| ParseError::at(definition, score, expected))?;
+                    moves.push(Move {
+                        name: name.to_string(),
+                        their: their_symbol,
+                        our: our_symbol,
+                        score: points,
+                    });
+                    names.push((name, beats.to_vec()));
+                }
+                _ => {
+                    let expected = "a move like `rock A X 1 scissors` or `outcomes X Y Z`";
+                    return Err(ParseError::at(definition, line.trim(), expected));
+                }
+            }
+        }
+
+        let n = moves.len();
+        if n < 3 || n.is_multiple_of(2) {
+            let end = &definition[definition.len()..];
+            let expected = format!("an odd number of moves (not {n})");
+            return Err(ParseError::at(definition, end, expected));
+        }
+        let mut beats = vec![vec![false; n]; n];
+        for (a, (name, beaten)) in names.iter().enumerate() {
+            for &other in beaten {
+                let b = names
+                    .iter()
+                    .position(|(n, _)| *n == other)
+                    .ok_or_else(|| ParseError::at(definition, other, "the name of a move"))?;
+                if a == b || beats[a][b] {
+                    let expected = format!("the name of a move {name} doesn't beat yet");
+                    return Err(ParseError::at(definition, other, expected));
+                }
+                beats[a][b] = true;
+            }
+        }
+        for (a, &(name, ref beaten)) in names.iter().enumerate() {
+            for b in a + 1..n {
+                let other = &moves[b].name;
+                if beats[a][b] && beats[b][a] {
+                    let expected = format!("a move that beats {other} or loses to it, not both");
+                    return Err(ParseError::at(definition, name, expected));
+                }
+                if !beats[a][b] && !beats[b][a] {
+                    let expected = format!("a move that beats {other} or loses to it");
+                    return Err(ParseError::at(definition, name, expected));
+                }
+            }
+            if beaten.len() != n / 2 {
+                let expected = format!("a move that beats {} of the {} others", n / 2, n - 1);
+                return Err(ParseError::at(definition, name, expected));
+            }
+        }
+        Ok(Game { moves, beats, outcomes })
+    }
+
+    pub fn their_move(&self, c: char) -> Option<usize> {
+        self.moves.iter().position(|m| m.their == c)
+    }
+
+    pub fn our_move(&self, c: char) -> Option<usize> {
+        self.moves.iter().position(|m| m.our == c)
+    }
+
+    pub fn outcome(&self, c: char) -> Option<Outcome> {
+        let i = self.outcomes.iter().position(|&o| o == c)?;
+        Some(OUTCOMES[i])
+    }
+
+    // The outcome of a round for us.
+    pub fn play(&self, their: usize, our: usize) -> Outcome {
+        if self.beats[our][their] {
+            Outcome::Win
+        } else if self.beats[their][our] {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    // The move that gives us `outcome`. With more than three moves there are several of them,
+    // and we take the one with the highest score (the first one if that's a tie too).
+    pub fn get_move(&self, their: usize, outcome: Outcome) -> usize {
+        (0..self.moves.len())
+            .filter(|&our| self.play(their, our) == outcome)
+            .max_by_key(|&our| (self.moves[our].score, std::cmp::Reverse(our)))
+            .unwrap()
+    }
+
+    // Parses a strategy guide for this game, with our moves in the second column (part 1)...
+    pub fn parse_moves(&self, content: &str) -> Result<Vec<(usize, usize)>, ParseError> {
+        let expected = one_of(self.moves.iter().map(|m| m.our));
+        self.parse_guide(content, |c| self.our_move(c), &expected)
+    }
+
+    // ... or the outcomes (part 2).
+    pub fn parse_outcomes(&self, content: &str) -> Result<Vec<(usize, Outcome)>, ParseError> {
+        let expected = one_of(self.outcomes.iter().copied());
+        self.parse_guide(content, |c| self.outcome(c), &expected)
+    }
+
+    fn parse_guide<T>(
+        &self,
+        content: &str,
+        second: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Vec<(usize, T)>, ParseError> {
+        content
+            .lines()
+            .map(|line| {
+                let (first, rest) = line
+                    .split_once(' ')
+                    .ok_or_else(|| ParseError::at(content, line, "two columns like `A Y`"))?;
+                let their = read_char(first).and_then(|c| self.their_move(c)).ok_or_else(|| {
+                    ParseError::at(content, first, one_of(self.moves.iter().map(|m| m.their)))
+                })?;
+                let second = read_char(rest)
+                    .and_then(&second)
+                    .ok_or_else(|| ParseError::at(content, rest, expected))?;
+                Ok((their, second))
+            })
+            .collect()
+    }
+
+    // The total scores of guides parsed with `parse_moves()` and `parse_outcomes()`. The points
+    // for the moves are the ones of the game, only the outcomes and streaks come from `scoring`.
+    pub fn score_moves(&self, rounds: &[(usize, usize)], scoring: &Scoring) -> u64 {
+        let mut tally = Tally::new(scoring);
+        for &(their, our) in rounds {
+            tally.add(self.moves[our].score, self.play(their, our));
+        }
+        tally.total
+    }
+
+    pub fn score_outcomes(&self, rounds: &[(usize, Outcome)], scoring: &Scoring) -> u64 {
+        let mut tally = Tally::new(scoring);
+        for &(their, outcome) in rounds {
+            tally.add(self.moves[self.get_move(their, outcome)].score, outcome);
+        }
+        tally.total
+    }
+}
+
+fn symbol(definition: &str, s: &str) -> Result<char, ParseError> {
+    read_char(s).ok_or_else(|| ParseError::at(definition, s, "a single character"))
+}
+
+// "one of A, B or C", for the errors.
+fn one_of(symbols: impl Iterator<Item = char>) -> String {
+    let symbols: Vec<String> = symbols.map(|c| c.to_string()).collect();
+    let (last, rest) = symbols.split_last().unwrap();
+    format!("one of {} or {last}", rest.join(", "))
+}
+
+#[test]
+fn test_presets() {
+    for (name, _) in PRESETS {
+        let game = Game::preset(name).unwrap();
+        let n = game.moves.len();
+        for their in 0..n {
+            // Every move wins against half of the others, and we can always get every outcome.
+            let wins = (0..n).filter(|&our| game.play(their, our) == Outcome::Win).count();
+            assert_eq!(wins, n / 2, "{name}");
+            for outcome in OUTCOMES {
+                assert_eq!(game.play(their, game.get_move(their, outcome)), outcome, "{name}");
+            }
+        }
+    }
+    assert!(Game::preset("chess").is_none());
+}
+
+// The same answers as with the puzzle's rules.
+#[test]
+fn test_rock_paper_scissors() {
+    use common::Solution;
+    let game = Game::rock_paper_scissors();
+    let scoring = Scoring::default();
+    for input in [include_str!("../example.txt"), crate::INPUT] {
+        let rounds = crate::Day2::parse(input).unwrap();
+        let moves = game.parse_moves(input).unwrap();
+        assert_eq!(game.score_moves(&moves, &scoring), crate::Day2::part1(&rounds));
+        let outcomes = game.parse_outcomes(input).unwrap();
+        assert_eq!(game.score_outcomes(&outcomes, &scoring), crate::Day2::part2(&rounds));
+    }
+}
+
+#[test]
+fn test_lizard_spock() {
+    let game = Game::preset("rpsls").unwrap();
+    let scoring = Scoring::default();
+    // Paper against rock (2 + 6), Spock against lizard (5 + 0), Spock against itself (5 + 3).
+    let guide = "A W\nD Z\nE Z\n";
+    assert_eq!(game.score_moves(&game.parse_moves(guide).unwrap(), &scoring), 21);
+    // Against Spock, we win with paper or lizard, and take lizard for its score (4 + 6). We lose
+    // against rock with scissors or lizard, lizard again (4 + 0). Lizard against lizard (4 + 3).
+    let guide = "E Z\nA X\nD Y\n";
+    assert_eq!(game.score_outcomes(&game.parse_outcomes(guide).unwrap(), &scoring), 21);
+    let err = game.parse_moves("A Y\nF Z\n").unwrap_err();
+    assert_eq!((err.line, err.column, err.expected.as_str()), (2, 1, "one of A, B, C, D or E"));
+    let err = game.parse_outcomes("A V\n").unwrap_err();
+    assert_eq!((err.column, err.expected.as_str()), (3, "one of X, Y or Z"));
+}
+
+#[test]
+fn test_definition_errors() {
+    let err = |definition: &str| {
+        let err = Game::parse(definition).unwrap_err();
+        (err.line, err.column, err.expected)
+    };
+    assert_eq!(err("rock A X 1 scissors\npaper B Y 2 rock\n").2, "an odd number of moves (not 2)");
+    assert_eq!(err("rock A X 1\n").2, "an odd number of moves (not 1)");
+    let (line, column, _) = err("rock A X 1 scissors\npaper AB Y 2 rock\n");
+    assert_eq!((line, column), (2, 7));
+    let (line, column, _) = err("rock A X 1 scissors\npaper A Y 2 rock\n");
+    assert_eq!((line, column), (2, 7));
+    let (line, column, _) = err("rock A X one scissors\n");
+    assert_eq!((line, column), (1, 10));
+    let (line, column, expected) = err("rock A X 4294967295 scissors\n");
+    assert_eq!((line, column, expected.as_str()), (1, 10, "a score up to 1000000"));
+    let (line, column, _) = err("rock A X 1 scissors\npaper B Y 2 rock\nscissors C Z 3 stone\n");
+    assert_eq!((line, column), (3, 16));
+    // Rock and paper beat each other, and nobody beats scissors.
+    let expected = "a move that beats paper or loses to it, not both";
+    let definition = "rock A X 1 paper\npaper B Y 2 rock\nscissors C Z 3 paper\n";
+    assert_eq!(err(definition), (1, 1, expected.to_string()));
+    // Rock beats both of the others, and scissors can't beat anything.
+    let definition = "rock A X 1 paper scissors\npaper B Y 2 scissors\nscissors C Z 3\n";
+    assert_eq!(err(definition), (1, 1, "a move that beats 1 of the 2 others".to_string()));
+    let (line, column, _) = err("outcomes X Y X\n");
+    assert_eq!((line, column), (1, 14));
+    let (line, _, _) = err("rock A X 1 scissors\nrock\n");
+    assert_eq!(line, 2);
+}
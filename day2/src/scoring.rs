@@ -0,0 +1,166 @@
+// How the rounds are scored. The puzzle gives 1, 2 or 3 points for our move (rock, paper or
+// scissors) and 0, 3 or 6 points for the outcome (a loss, a draw or a win). These used to be the
+// values of the `Choice` and `Outcome` enums, so we couldn't score a guide differently without
+// changing the enums. Now the puzzle's scores are the default, and a run can use others with
+// `--scoring` (see `FromStr` below):
+//
+//     cargo run -p day2 -- --scoring win=10,draw=5,streak=3:4
+//
+// There can also be a bonus for winning streaks: once we've won `length` rounds in a row, every
+// win gets `bonus` extra points, until we don't win a round.
+//
+// The totals are u64s, and no setting can be more than `MAX_POINTS`. A round is then worth at most
+// a few million points, and a guide would need trillions of rounds to overflow the total.
+
+use std::str::FromStr;
+
+use crate::{Choice, Outcome};
+
+pub const MAX_POINTS: u32 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    pub length: u32,
+    pub bonus: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scoring {
+    // The points for rock, paper and scissors...
+    pub choices: [u32; 3],
+    // ... and for a loss, a draw and a win.
+    pub outcomes: [u32; 3],
+    pub streak: Option<Streak>,
+}
+
+impl Default for Scoring {
+    fn default() -> Self {
+        Scoring {
+            choices: [1, 2, 3],
+            outcomes: [0, 3, 6],
+            streak: None,
+        }
+    }
+}
+
+impl Scoring {
+    pub fn choice(&self, choice: Choice) -> u32 {
+        self.choices[choice.index()]
+    }
+
+    pub fn outcome(&self, outcome: Outcome) -> u32 {
+        self.outcomes[outcome.index()]
+    }
+
+    // The score of all the rounds of a game (in order, for the streaks): our choice and the
+    // outcome of each.
+    pub fn total(&self, rounds: impl IntoIterator<Item = (Choice, Outcome)>) -> u64 {
+        let mut tally = Tally::new(self);
+        for (choice, outcome) in rounds {
+            tally.add(self.choice(choice), outcome);
+        }
+        tally.total
+    }
+}
+
+// Adds up the score of a game round by round, and keeps track of the winning streak.
+pub struct Tally<'a> {
+    scoring: &'a Scoring,
+    // The number of wins in a row so far.
+    wins: u32,
+    pub total: u64,
+}
+
+impl<'a> Tally<'a> {
+    pub fn new(scoring: &'a Scoring) -> Self {
+        Tally { scoring, wins: 0, total: 0 }
+    }
+
+    // The points for the move are passed in, because in other games they come from the
+    // definition of the game instead (see `rules.rs`).
+    pub fn add(&mut self, move_points: u32, outcome: Outcome) {
+        self.total += u64::from(move_points) + u64::from(self.scoring.outcome(outcome));
+        if outcome != Outcome::Win {
+            self.wins = 0;
+            return;
+        }
+        self.wins += 1;
+        match self.scoring.streak {
+            Some(streak) if self.wins >= streak.length => self.total += u64::from(streak.bonus),
+            _ => {}
+        }
+    }
+}
+
+// Parses a list of the scores that are different from the default ones, like
+// `rock=2,win=10,streak=3:4` (a bonus of 4 for every win once we've won 3 rounds in a row).
+// `default` (or nothing at all) is the puzzle's scoring.
+impl FromStr for Scoring {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut scoring = Scoring::default();
+        if s.is_empty() || s == "default" {
+            return Ok(scoring);
+        }
+        let number = |key: &str, value: &str| {
+            let err = format!("{key} needs a number of points up to {MAX_POINTS}, not {value:?}");
+            value.parse::<u32>().ok().filter(|&points| points <= MAX_POINTS).ok_or(err)
+        };
+        for setting in s.split(',') {
+            let (key, value) = setting
+                .split_once('=')
+                .ok_or_else(|| format!("expected a setting like `win=6`, not {setting:?}"))?;
+            let (key, value) = (key.trim(), value.trim());
+            match key {
+                "rock" => scoring.choices[0] = number(key, value)?,
+                "paper" => scoring.choices[1] = number(key, value)?,
+                "scissors" => scoring.choices[2] = number(key, value)?,
+                "loss" => scoring.outcomes[0] = number(key, value)?,
+                "draw" => scoring.outcomes[1] = number(key, value)?,
+                "win" => scoring.outcomes[2] = number(key, value)?,
+                "streak" => {
+                    let (length, bonus) = value.split_once(':').ok_or_else(|| {
+                        format!("streak needs a length and a bonus like `3:4`, not {value:?}")
+                    })?;
+                    let length = length.parse::<u32>().ok().filter(|&length| length > 0);
+                    let length = length.ok_or("a streak has to be a number of wins, at least 1")?;
+                    scoring.streak = Some(Streak { length, bonus: number(key, bonus)? });
+                }
+                _ => {
+                    let expected = "rock, paper, scissors, loss, draw, win or streak";
+                    return Err(format!("unknown score {key:?} (expected {expected})"));
+                }
+            }
+        }
+        Ok(scoring)
+    }
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!("".parse(), Ok(Scoring::default()));
+    assert_eq!("default".parse(), Ok(Scoring::default()));
+    let scoring: Scoring = "rock=0, win=10,streak=3:4".parse().unwrap();
+    assert_eq!(scoring.choices, [0, 2, 3]);
+    assert_eq!(scoring.outcomes, [0, 3, 10]);
+    assert_eq!(scoring.streak, Some(Streak { length: 3, bonus: 4 }));
+    assert!("win".parse::<Scoring>().is_err());
+    assert!("win=-1".parse::<Scoring>().is_err());
+    assert!("paper=4294967295".parse::<Scoring>().is_err());
+    assert!("streak=2:1000001".parse::<Scoring>().is_err());
+    assert_eq!("win=1000000".parse::<Scoring>().unwrap().outcomes[2], MAX_POINTS);
+    assert!("lizard=5".parse::<Scoring>().is_err());
+    assert!("streak=4".parse::<Scoring>().is_err());
+    assert!("streak=0:4".parse::<Scoring>().is_err());
+}
+
+#[test]
+fn test_streak() {
+    let scoring: Scoring = "streak=2:5".parse().unwrap();
+    let (win, loss) = ((Choice::Rock, Outcome::Win), (Choice::Rock, Outcome::Loss));
+    // The second and third win in a row get the bonus, the first win after the loss doesn't.
+    let rounds = [win, win, win, loss, win];
+    assert_eq!(scoring.total(rounds), 4 * 7 + 1 + 2 * 5);
+    assert_eq!(Scoring::default().total(rounds), 4 * 7 + 1);
+}
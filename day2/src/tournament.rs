@@ -0,0 +1,413 @@
+// A Rock-Paper-Scissors tournament: strategies play many rounds against each other, and we see
+// which of them wins most often. Every strategy plays a match against every other one.
+//
+// Some of the strategies are random, so there's a small random number generator here as well. It
+// takes a seed, so a tournament can be repeated exactly (the `rand` crate would be overkill).
+
+use std::fmt;
+
+use crate::scoring::{Scoring, Tally};
+use crate::{get_move, play, read_choice, Choice, Outcome};
+
+// SplitMix64, which is tiny, fast and good enough for games. See
+// https://prng.di.unimi.it/splitmix64.c
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // A random choice. The modulo makes some of them a tiny bit more likely, not enough to matter.
+    pub fn choice(&mut self) -> Choice {
+        Choice::ALL[(self.next_u64() % 3) as usize]
+    }
+}
+
+pub trait Strategy {
+    fn name(&self) -> &str;
+    // Called before every match, to forget what happened in the last one.
+    fn reset(&mut self) {}
+    fn choose(&mut self, rng: &mut Rng) -> Choice;
+    // What both players chose in a round, for the strategies that learn from it.
+    fn observe(&mut self, _ours: Choice, _theirs: Choice) {}
+}
+
+// The choice that beats `choice`.
+fn beat(choice: Choice) -> Choice {
+    get_move(&choice, &Outcome::Win)
+}
+
+// Plays the same choices over and over, e.g. a column of the strategy guide.
+pub struct Fixed {
+    name: String,
+    choices: Vec<Choice>,
+    next: usize,
+}
+
+impl Fixed {
+    pub fn new(name: &str, choices: Vec<Choice>) -> Fixed {
+        Fixed { name: name.to_string(), choices, next: 0 }
+    }
+
+    // The moves of the opponent in the guide...
+    pub fn opponent(rounds: &[(Choice, char)]) -> Fixed {
+        Fixed::new("opponent", rounds.iter().map(|(their_choice, _)| *their_choice).collect())
+    }
+
+    // ... and ours, reading the second column like in part 1.
+    pub fn guide(rounds: &[(Choice, char)]) -> Fixed {
+        let choices = rounds.iter().map(|(_, second)| read_choice(*second).unwrap()).collect();
+        Fixed::new("guide", choices)
+    }
+}
+
+impl Strategy for Fixed {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn reset(&mut self) {
+        self.next = 0;
+    }
+
+    // Without any choices, there's nothing to repeat.
+    fn choose(&mut self, rng: &mut Rng) -> Choice {
+        if self.choices.is_empty() {
+            return rng.choice();
+        }
+        let choice = self.choices[self.next % self.choices.len()];
+        self.next += 1;
+        choice
+    }
+}
+
+pub struct Random;
+
+impl Strategy for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn choose(&mut self, rng: &mut Rng) -> Choice {
+        rng.choice()
+    }
+}
+
+// Counts what the opponent chose so far, and beats the choice they made most often.
+#[derive(Default)]
+pub struct Frequency {
+    counts: [u64; 3],
+}
+
+impl Strategy for Frequency {
+    fn name(&self) -> &str {
+        "frequency"
+    }
+
+    fn reset(&mut self) {
+        self.counts = [0; 3];
+    }
+
+    fn choose(&mut self, rng: &mut Rng) -> Choice {
+        if self.counts == [0; 3] {
+            return rng.choice();
+        }
+        // The first one if there's a tie.
+        let most = (0..3).max_by_key(|&i| (self.counts[i], std::cmp::Reverse(i))).unwrap();
+        beat(Choice::ALL[most])
+    }
+
+    fn observe(&mut self, _ours: Choice, theirs: Choice) {
+        self.counts[theirs.index()] += 1;
+    }
+}
+
+// Keeps the choice that won the last round. After a loss or a draw, it switches to the choice
+// that would have beaten the opponent's last one.
+#[derive(Default)]
+pub struct WinStayLoseShift {
+    last: Option<(Choice, Choice)>,
+}
+
+impl Strategy for WinStayLoseShift {
+    fn name(&self) -> &str {
+        "win-stay/lose-shift"
+    }
+
+    fn reset(&mut self) {
+        self.last = None;
+    }
+
+    fn choose(&mut self, rng: &mut Rng) -> Choice {
+        match self.last {
+            None => rng.choice(),
+            Some((ours, theirs)) if play(&theirs, &ours) == Outcome::Win => ours,
+            Some((_, theirs)) => beat(theirs),
+        }
+    }
+
+    fn observe(&mut self, ours: Choice, theirs: Choice) {
+        self.last = Some((ours, theirs));
+    }
+}
+
+// Predicts the opponent's next choice from their last one: `counts[a][b]` is how often they chose
+// `b` after `a`. We start with the counts of the opponent column of a guide, and keep counting
+// during the match. Then we beat the most likely next choice.
+pub struct Markov {
+    trained: [[u64; 3]; 3],
+    counts: [[u64; 3]; 3],
+    last: Option<Choice>,
+}
+
+impl Markov {
+    pub fn train(rounds: &[(Choice, char)]) -> Markov {
+        let mut trained = [[0; 3]; 3];
+        for pair in rounds.windows(2) {
+            trained[pair[0].0.index()][pair[1].0.index()] += 1;
+        }
+        Markov { trained, counts: trained, last: None }
+    }
+}
+
+impl Strategy for Markov {
+    fn name(&self) -> &str {
+        "markov"
+    }
+
+    fn reset(&mut self) {
+        self.counts = self.trained;
+        self.last = None;
+    }
+
+    fn choose(&mut self, rng: &mut Rng) -> Choice {
+        let Some(last) = self.last else {
+            return rng.choice();
+        };
+        let row = self.counts[last.index()];
+        if row == [0; 3] {
+            return rng.choice();
+        }
+        let next = (0..3).max_by_key(|&i| (row[i], std::cmp::Reverse(i))).unwrap();
+        beat(Choice::ALL[next])
+    }
+
+    fn observe(&mut self, _ours: Choice, theirs: Choice) {
+        if let Some(last) = self.last {
+            self.counts[last.index()][theirs.index()] += 1;
+        }
+        self.last = Some(theirs);
+    }
+}
+
+// A round of a match between players `a` and `b`, and the outcome for `a`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub a: Choice,
+    pub b: Choice,
+    pub outcome: Outcome,
+}
+
+pub struct Match {
+    // The indices of the players in the tournament.
+    pub a: usize,
+    pub b: usize,
+    pub rounds: Vec<Round>,
+}
+
+impl Match {
+    // The points of both players.
+    pub fn scores(&self, scoring: &Scoring) -> (u64, u64) {
+        let (mut a, mut b) = (Tally::new(scoring), Tally::new(scoring));
+        for round in &self.rounds {
+            a.add(scoring.choice(round.a), round.outcome);
+            b.add(scoring.choice(round.b), round.outcome.reverse());
+        }
+        (a.total, b.total)
+    }
+}
+
+pub fn play_match(
+    a: &mut dyn Strategy,
+    b: &mut dyn Strategy,
+    rounds: usize,
+    rng: &mut Rng,
+) -> Vec<Round> {
+    a.reset();
+    b.reset();
+    (0..rounds)
+        .map(|_| {
+            let (choice_a, choice_b) = (a.choose(rng), b.choose(rng));
+            a.observe(choice_a, choice_b);
+            b.observe(choice_b, choice_a);
+            Round { a: choice_a, b: choice_b, outcome: play(&choice_b, &choice_a) }
+        })
+        .collect()
+}
+
+// Every player against every other one, `rounds` rounds each.
+pub fn round_robin(players: &mut [Box<dyn Strategy>], rounds: usize, seed: u64) -> Vec<Match> {
+    let mut rng = Rng::new(seed);
+    let mut matches = vec![];
+    for a in 0..players.len() {
+        for b in a + 1..players.len() {
+            // Borrowing two elements of the slice mutably at the same time needs split_at_mut().
+            let (left, right) = players.split_at_mut(b);
+            let log = play_match(left[a].as_mut(), right[0].as_mut(), rounds, &mut rng);
+            matches.push(Match { a, b, rounds: log });
+        }
+    }
+    matches
+}
+
+// How a player did over all their matches. These are u64s, because the points of a long
+// tournament (or one with large custom scores) don't fit into a u32.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Standing {
+    pub wins: u64,
+    pub draws: u64,
+    pub losses: u64,
+    pub points: u64,
+}
+
+impl Standing {
+    pub fn win_rate(&self) -> f64 {
+        let played = self.wins + self.draws + self.losses;
+        if played == 0 {
+            0.0
+        } else {
+            self.wins as f64 / played as f64
+        }
+    }
+}
+
+pub fn standings(players: usize, matches: &[Match], scoring: &Scoring) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = (0..players).map(|_| Standing::default()).collect();
+    for m in matches {
+        let (points_a, points_b) = m.scores(scoring);
+        standings[m.a].points += points_a;
+        standings[m.b].points += points_b;
+        for round in &m.rounds {
+            match round.outcome {
+                Outcome::Win => {
+                    standings[m.a].wins += 1;
+                    standings[m.b].losses += 1;
+                }
+                Outcome::Loss => {
+                    standings[m.a].losses += 1;
+                    standings[m.b].wins += 1;
+                }
+                Outcome::Draw => {
+                    standings[m.a].draws += 1;
+                    standings[m.b].draws += 1;
+                }
+            }
+        }
+    }
+    standings
+}
+
+// A line of the log, e.g. `rock      paper     loss`.
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |s: String| s.to_lowercase();
+        write!(
+            f,
+            "{:<9} {:<9} {}",
+            name(format!("{:?}", self.a)),
+            name(format!("{:?}", self.b)),
+            name(format!("{:?}", self.outcome))
+        )
+    }
+}
+
+#[test]
+fn test_rng() {
+    let numbers = |seed| {
+        let mut rng = Rng::new(seed);
+        (0..5).map(|_| rng.next_u64()).collect::<Vec<u64>>()
+    };
+    assert_eq!(numbers(42), numbers(42));
+    assert_ne!(numbers(42), numbers(43));
+    // Each choice about a third of the time.
+    let mut rng = Rng::new(1);
+    let mut counts = [0; 3];
+    for _ in 0..3000 {
+        counts[rng.choice().index()] += 1;
+    }
+    assert!(counts.iter().all(|&count| (900..1100).contains(&count)), "{counts:?}");
+}
+
+// Following the guide against the opponent of the guide gives the score of part 1.
+#[test]
+fn test_guide() {
+    use common::Solution;
+    for input in [include_str!("../example.txt"), crate::INPUT] {
+        let rounds = crate::Day2::parse(input).unwrap();
+        let mut rng = Rng::new(0);
+        let (mut opponent, mut guide) = (Fixed::opponent(&rounds), Fixed::guide(&rounds));
+        let log = play_match(&mut opponent, &mut guide, rounds.len(), &mut rng);
+        let m = Match { a: 0, b: 1, rounds: log };
+        assert_eq!(m.scores(&Scoring::default()).1, crate::Day2::part1(&rounds));
+    }
+}
+
+// The learning strategies beat an opponent that always plays rock, after a round or two (the
+// Markov strategy needs two of their choices to see the first one follow another).
+#[test]
+fn test_learning() {
+    let mut rock = Fixed::new("rock", vec![Choice::Rock]);
+    let players: [Box<dyn Strategy>; 3] = [
+        Box::new(Frequency::default()),
+        Box::new(WinStayLoseShift::default()),
+        Box::new(Markov::train(&[])),
+    ];
+    for mut player in players {
+        let log = play_match(&mut rock, player.as_mut(), 100, &mut Rng::new(7));
+        assert!(log[2..].iter().all(|round| round.outcome == Outcome::Loss), "{}", player.name());
+    }
+}
+
+// Trained on a guide where the opponent goes rock, paper, scissors, the Markov strategy knows
+// their next move from the start.
+#[test]
+fn test_markov() {
+    let cycle = [Choice::Rock, Choice::Paper, Choice::Scissors];
+    let guide: Vec<(Choice, char)> = cycle.iter().cycle().take(9).map(|&c| (c, 'X')).collect();
+    let mut opponent = Fixed::new("cycle", cycle.to_vec());
+    let mut markov = Markov::train(&guide);
+    let log = play_match(&mut opponent, &mut markov, 30, &mut Rng::new(0));
+    assert!(log[1..].iter().all(|round| round.outcome == Outcome::Loss));
+}
+
+#[test]
+fn test_round_robin() {
+    let players = || -> Vec<Box<dyn Strategy>> {
+        vec![
+            Box::new(Random),
+            Box::new(Frequency::default()),
+            Box::new(WinStayLoseShift::default()),
+        ]
+    };
+    let matches = round_robin(&mut players(), 50, 3);
+    assert_eq!(matches.iter().map(|m| (m.a, m.b)).collect::<Vec<_>>(), [(0, 1), (0, 2), (1, 2)]);
+    // The same seed plays the same tournament.
+    let again = round_robin(&mut players(), 50, 3);
+    assert!(matches.iter().zip(&again).all(|(m1, m2)| m1.rounds == m2.rounds));
+    let standings = standings(3, &matches, &Scoring::default());
+    for standing in &standings {
+        assert_eq!(standing.wins + standing.draws + standing.losses, 100);
+    }
+    let wins: u64 = standings.iter().map(|s| s.wins).sum();
+    let losses: u64 = standings.iter().map(|s| s.losses).sum();
+    assert_eq!(wins, losses);
+}
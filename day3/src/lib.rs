@@ -0,0 +1,228 @@
+use std::io::BufRead;
+
+use common::{LineReader, ParseError, Solution, StreamError, StreamSolution};
+
+use rucksack::{badges, Items, Rucksack};
+
+pub mod rucksack;
+
+// I learned this neat trick from looking at other people's AoC entries:
+// You can include a string at compile time.
+pub const INPUT: &str = include_str!("../input.txt");
+
+fn priority(c: char) -> u32 {
+    if c.is_ascii_uppercase() {
+        c as u32 - 38
+    } else {
+        c as u32 - 96
+    }
+}
+
+// Rucksacks can only contain letters. `input` is the whole input, for the error position.
+fn check_rucksack(input: &str, line: &str) -> Result<(), ParseError> {
+    if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        let item = &line[i..i + c.len_utf8()];
+        return Err(ParseError::at(input, item, "an item (`a` to `z` or `A` to `Z`)"));
+    }
+    Ok(())
+}
+
+// Part 1 used to compare every item of the first compartment with every item of the second one,
+// and part 2 intersected HashSets of the three rucksacks of a group. Both are intersections of
+// sets of items, which `rucksack.rs` does with bits instead. That also works for any number of
+// compartments and any size of groups, not just the 2 and 3 of the puzzle.
+
+// The sum of the priorities of the items that are in all `compartments` of a rucksack...
+pub fn part1_with(rucksacks: &[Rucksack], compartments: usize) -> u64 {
+    rucksacks.iter().map(|r| u64::from(r.common_items(compartments).max_priority())).sum()
+}
+
+// ... and of the badges of the groups of `group` rucksacks. Both are 0 for 0 compartments or
+// groups of 0, though the day 3 binary doesn't allow them.
+pub fn part2_with(rucksacks: &[Rucksack], group: usize) -> u64 {
+    if group == 0 {
+        return 0;
+    }
+    // chunks_exact() is a method of slice, and a `&Vec<Rucksack>` can be used as a slice.
+    // Unlike chunks(), it leaves out an incomplete group at the end.
+    rucksacks.chunks_exact(group).map(|group| u64::from(badges(group).max_priority())).sum()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Rucksack>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        let mut rucksacks = vec![];
+        for line in input.lines() {
+            check_rucksack(input, line)?;
+            rucksacks.push(Rucksack::new(line));
+        }
+        Ok(rucksacks)
+    }
+
+    fn part1(rucksacks: &Self::Input) -> u64 {
+        part1_with(rucksacks, 2)
+    }
+
+    fn part2(rucksacks: &Self::Input) -> u64 {
+        part2_with(rucksacks, 3)
+    }
+}
+
+// Part 1 looks at one rucksack at a time and part 2 at groups of three. For a group, we only keep
+// the items all of its rucksacks so far have in common. The sums are u64s, because a few
+// gigabytes of rucksacks are worth more than `u32::MAX`.
+impl StreamSolution for Day3 {
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(u64, u64), StreamError> {
+        let mut lines = LineReader::new(reader);
+        let (mut badge, mut size) = (Items::ALL, 0);
+        let mut score = (0, 0);
+        while let Some((number, line)) = lines.next_line()? {
+            check_rucksack(line, line).map_err(|err| err.on_line(number))?;
+            let rucksack = Rucksack::new(line);
+            score.0 += u64::from(rucksack.common_items(2).max_priority());
+            badge = badge.intersection(rucksack.items());
+            size += 1;
+            if size == 3 {
+                score.1 += u64::from(badge.max_priority());
+                (badge, size) = (Items::ALL, 0);
+            }
+        }
+        Ok(score)
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day3::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day3::part1(&input), 157);
+    assert_eq!(Day3::part2(&input), 70);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day3::parse(INPUT).unwrap();
+    assert_eq!(Day3::part1(&input), 8088);
+    assert_eq!(Day3::part2(&input), 2522);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day3>(INPUT);
+}
+
+// Reading the input bit by bit gives the same answers as parsing all of it first.
+#[test]
+fn test_stream() {
+    for input in [include_str!("../example.txt"), INPUT, "abca\nb\nb\nb\n"] {
+        let parsed = Day3::parse(input).unwrap();
+        let expected = (Day3::part1(&parsed), Day3::part2(&parsed));
+        assert_eq!(Day3::solve_stream(input.as_bytes()).unwrap(), expected);
+    }
+}
+
+// Other compartments and groups than the puzzle's.
+#[test]
+fn test_sizes() {
+    let rucksacks = Day3::parse(include_str!("../example.txt")).unwrap();
+    // With one compartment, all items are in it. In groups of one, they're all badges.
+    let highest: u64 = rucksacks.iter().map(|r| u64::from(r.items().max_priority())).sum();
+    assert_eq!(part1_with(&rucksacks, 1), highest);
+    assert_eq!(part2_with(&rucksacks, 1), highest);
+    // The badges of the two groups are different, so there's none in a group of all six.
+    assert_eq!(part2_with(&rucksacks, 6), 0);
+    // And there's no complete group of seven.
+    assert_eq!(part2_with(&rucksacks, 7), 0);
+    assert_eq!((part1_with(&rucksacks, 0), part2_with(&rucksacks, 0)), (0, 0));
+    // Three compartments, with `a`, `b` and `c` in all of them, and only `b` and `c`.
+    let rucksacks = Day3::parse("abcabcabc\nabcXbcYbc\n").unwrap();
+    assert_eq!(part1_with(&rucksacks, 3), u64::from(priority('c') + priority('c')));
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// A group of three rucksacks where we know the answers: every rucksack has its own lowercase
+// letters (different ones in each compartment), so the only item in both compartments and the
+// only badge are the uppercase letters we put in.
+#[cfg(test)]
+fn arb_group() -> impl Strategy<Value = (Vec<String>, u64, u64)> {
+    use proptest::collection::vec;
+    let halves = (0..6usize).prop_flat_map(|n| (vec(0..4u8, n), vec(0..4u8, n)));
+    let uppercase: Vec<char> = ('A'..='Z').collect();
+    let special = proptest::sample::subsequence(uppercase, 4).prop_shuffle();
+    (special, [halves.clone(), halves.clone(), halves]).prop_map(|(special, halves)| {
+        let badge = special[3];
+        let mut rucksacks = vec![];
+        for (k, (front, back)) in halves.into_iter().enumerate() {
+            let letter = |i: u8| (b'a' + 8 * k as u8 + i) as char;
+            let mut front: String = front.iter().map(|&i| letter(i)).collect();
+            let mut back: String = back.iter().map(|&i| letter(4 + i)).collect();
+            front.push(special[k]);
+            front.push(badge);
+            back.push(special[k]);
+            back.push(letter(4));
+            rucksacks.push(front + &back);
+        }
+        let part1 = special[..3].iter().map(|&c| u64::from(priority(c))).sum();
+        (rucksacks, part1, u64::from(priority(badge)))
+    })
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_known_answers(groups in proptest::collection::vec(arb_group(), 0..10)) {
+        let input: String = groups.iter().flat_map(|g| &g.0).map(|r| format!("{r}\n")).collect();
+        let rucksacks = Day3::parse(&input).unwrap();
+        prop_assert_eq!(Day3::part1(&rucksacks), groups.iter().map(|g| g.1).sum::<u64>());
+        prop_assert_eq!(Day3::part2(&rucksacks), groups.iter().map(|g| g.2).sum::<u64>());
+    }
+
+    // Any letters at all, with odd lengths, no common items and an incomplete last group.
+    #[test]
+    fn test_any_rucksacks(rucksacks in proptest::collection::vec("[a-zA-Z]{0,20}", 0..10)) {
+        let input: String = rucksacks.iter().map(|r| format!("{r}\n")).collect();
+        let parsed = Day3::parse(&input).unwrap();
+        let expected = (Day3::part1(&parsed), Day3::part2(&parsed));
+        prop_assert_eq!(Day3::solve_stream(input.as_bytes()).unwrap(), expected);
+        let rucksacks = parsed;
+        prop_assert!(Day3::part1(&rucksacks) <= 52 * rucksacks.len() as u64);
+        prop_assert!(Day3::part2(&rucksacks) <= 52 * (rucksacks.len() / 3) as u64);
+    }
+
+    // The bits give the same answers as sets of characters, for any number of compartments and
+    // any size of groups.
+    #[test]
+    fn test_against_sets(
+        rucksacks in proptest::collection::vec("[a-zA-Z]{0,20}", 0..10),
+        k in 1..5usize,
+        n in 1..5usize,
+    ) {
+        use std::collections::HashSet;
+        let common = |sets: Vec<HashSet<char>>| {
+            let common = sets.into_iter().reduce(|a, b| &a & &b).unwrap_or_default();
+            common.into_iter().map(priority).max().map_or(0, u64::from)
+        };
+        let part1: u64 = rucksacks
+            .iter()
+            .map(|r| {
+                let split = |i: usize| &r[i * r.len() / k..(i + 1) * r.len() / k];
+                common((0..k).map(|i| split(i).chars().collect()).collect())
+            })
+            .sum();
+        let part2: u64 = rucksacks
+            .chunks_exact(n)
+            .map(|group| common(group.iter().map(|r| r.chars().collect()).collect()))
+            .sum();
+        let input: String = rucksacks.iter().map(|r| format!("{r}\n")).collect();
+        let parsed = Day3::parse(&input).unwrap();
+        prop_assert_eq!(part1_with(&parsed, k), part1);
+        prop_assert_eq!(part2_with(&parsed, n), part2);
+    }
+}
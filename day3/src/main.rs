@@ -1,54 +1,39 @@
-use std::collections::HashSet;
+// Prints both answers like the other days. The rucksacks can also have a different number of
+// compartments (for part 1) or the elves be in groups of a different size (for part 2):
+//
+//     cargo run -p day3 -- --compartments 3 --group 4 [input]
 
-fn priority(c: char) -> u32 {
-    if c.is_ascii_uppercase() {
-        c as u32 - 38
-    } else {
-        c as u32 - 96
-    }
-}
+use std::env;
+use std::process;
 
-fn main() {
-    // I learned this neat trick from looking at other people's AoC entries:
-    // You can include a string at compile time.
-    let input = include_str!("../input.txt");
+use day3::Day3;
 
-    let mut score = 0;
-    for line in input.lines() {
-        let (front, back) = line.split_at(line.len()/2);
-        'line: for c1 in front.chars() {
-            for c2 in back.chars() {
-                if c1 == c2 {
-                    score += priority(c1);
-                    break 'line;
-                }
-            }
+fn main() {
+    let mut args = env::args().skip(1);
+    let mut compartments = 2;
+    let mut group = 3;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--compartments" => compartments = count(&arg, args.next()),
+            "--group" => group = count(&arg, args.next()),
+            _ => path = Some(arg),
         }
     }
+    let input = common::input::load_or_exit(path.as_deref(), day3::INPUT);
+    let rucksacks = common::parse_or_exit::<Day3>(&input);
+    println!("part 1: {}", day3::part1_with(&rucksacks, compartments));
+    println!("part 2: {}", day3::part2_with(&rucksacks, group));
+}
 
-    println!("part 1: {score}");
-
-    // Let's use sets for part 2 instead of nested loops.
-
-    let mut score = 0;
-    // Before we can use chunks() (a method of slice), we need to
-    // turn the lines iterator back into a slice.
-    // We need to specify a type for collect(), but instead of `<Vec<&str>>`
-    // we can just say `<Vec<_>>`, the inner type will be inferred.
-    for group in input.lines().collect::<Vec<_>>().chunks(3) {
-        // We can also put the type annotation on the variable.
-        // This is equivalent to
-        //  let set1 = group[0].chars().collect::<HashSet<char>>();
-        let set1: HashSet<char> = group[0].chars().collect();
-        let set2: HashSet<char> = group[1].chars().collect();
-        let set3: HashSet<char> = group[2].chars().collect();
-        // intersection() does not return a new set, but an iterator over references
-        // to set items. So we have to use copied() to get copies of the items
-        // (turn `&char` back into `char`) and construct an intermediate set from that.
-        let tmp: HashSet<char> = set1.intersection(&set2).copied().collect();
-        let badge = set3.intersection(&tmp).next().unwrap();
-        score += priority(*badge);
+// There has to be at least one compartment, and at least one elf in a group.
+fn count(flag: &str, value: Option<String>) -> usize {
+    let value = value.unwrap_or_default();
+    match value.parse() {
+        Ok(n) if n > 0 => n,
+        _ => {
+            eprintln!("error: {flag} needs a number of at least 1, not {value:?}");
+            process::exit(2);
+        }
     }
-
-    println!("part 2: {score}");
 }
@@ -0,0 +1,114 @@
+// Rucksacks as sets of items. There are only 52 kinds of items, so a set of them fits into the
+// bits of a u64: bit `p - 1` is set if there's an item with priority `p`. The intersection of two
+// sets is then a single `&`, so we don't need nested loops or HashSets anymore, and it doesn't
+// matter how many compartments or rucksacks we intersect.
+
+use crate::priority;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Items(u64);
+
+impl Items {
+    // Every kind of item, the start for intersecting a few sets.
+    pub const ALL: Items = Items((1 << 52) - 1);
+
+    // The items have to be letters, or the shift over- or underflows. `parse()` checks that, so
+    // only this crate can make sets from strings, and everyone else gets them from `parse()`.
+    pub(crate) fn new(items: &str) -> Items {
+        Items(items.chars().fold(0, |bits, c| bits | 1 << (priority(c) - 1)))
+    }
+
+    pub fn intersection(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    // The puzzle always has a single item in the intersections, but other inputs can have more or
+    // none at all. Then we take the highest priority, or 0. It's the position of the highest bit.
+    pub fn max_priority(self) -> u32 {
+        u64::BITS - self.0.leading_zeros()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rucksack {
+    // The items in the order of the input, for splitting them into compartments, and as a set.
+    // Neither is public, so that a rucksack can't be made with anything but letters, and the two
+    // always have the same items.
+    contents: String,
+    items: Items,
+}
+
+impl Rucksack {
+    // Like `Items::new()`, the contents have to be letters (see `check_rucksack()`).
+    pub(crate) fn new(contents: &str) -> Rucksack {
+        Rucksack { contents: contents.to_string(), items: Items::new(contents) }
+    }
+
+    pub fn items(&self) -> Items {
+        self.items
+    }
+
+    // Splits the items into `k` compartments of (nearly) the same size. If the number of items
+    // isn't a multiple of `k`, the boundaries are rounded down, so the later compartments get the
+    // extra ones (like the second compartment with an odd number of items in the puzzle). Without
+    // any compartments, there are none.
+    pub fn compartments(&self, k: usize) -> Vec<Items> {
+        let n = self.contents.len();
+        // The items are ASCII letters, so we can split anywhere.
+        (0..k).map(|i| Items::new(&self.contents[i * n / k..(i + 1) * n / k])).collect()
+    }
+
+    // The items that are in all `k` compartments. There are no compartments to have any in
+    // common if `k` is 0 (instead of every kind of item, the start of the intersection).
+    pub fn common_items(&self, k: usize) -> Items {
+        if k == 0 {
+            return Items::default();
+        }
+        self.compartments(k).into_iter().fold(Items::ALL, Items::intersection)
+    }
+}
+
+// The items that all rucksacks of a group have, none if the group is empty.
+pub fn badges(group: &[Rucksack]) -> Items {
+    if group.is_empty() {
+        return Items::default();
+    }
+    group.iter().fold(Items::ALL, |items, rucksack| items.intersection(rucksack.items))
+}
+
+#[test]
+fn test_items() {
+    let items = Items::new("vJrwpWtwJgWr");
+    assert_eq!(items.len(), 8);
+    assert_eq!(items.max_priority(), priority('W'));
+    let common = items.intersection(Items::new("hcsFMMfFFhFp"));
+    assert_eq!((common.len(), common.max_priority()), (1, 16));
+    assert!(Items::new("abc").intersection(Items::new("XYZ")).is_empty());
+    assert_eq!(Items::default().max_priority(), 0);
+    assert_eq!(Items::ALL.len(), 52);
+    assert_eq!(Items::new("Z").max_priority(), 52);
+}
+
+#[test]
+fn test_compartments() {
+    let rucksack = Rucksack::new("abcdefg");
+    let split = |k| rucksack.compartments(k);
+    assert_eq!(split(1), [Items::new("abcdefg")]);
+    assert_eq!(split(2), [Items::new("abc"), Items::new("defg")]);
+    assert_eq!(split(3), [Items::new("ab"), Items::new("cd"), Items::new("efg")]);
+    // More compartments than items leaves some of them empty, so there's nothing in all of them.
+    assert!(rucksack.common_items(8).is_empty());
+    assert_eq!(Rucksack::new("aXaXaX").common_items(3), Items::new("aX"));
+    // No compartments at all, and no rucksacks in a group.
+    assert_eq!(rucksack.compartments(0), []);
+    assert!(rucksack.common_items(0).is_empty());
+    assert!(badges(&[]).is_empty());
+}
@@ -0,0 +1,157 @@
+use std::io::BufRead;
+
+use common::{LineReader, ParseError, Solution, StreamError, StreamSolution};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+type Pair = ((u32, u32), (u32, u32));
+
+// I refactored out these functions after solving the puzzle, and tried to write
+// them without using unwrap(). I learned that you can use the `?` early-return
+// operator with Option as well as Result, which is neat.
+// They take the whole input as well, so errors can point at the right line and column.
+fn parse_range(input: &str, s: &str) -> Result<(u32, u32), ParseError> {
+    let (lo, hi) = s
+        .split_once('-')
+        .ok_or_else(|| ParseError::at(input, s, "a range like `2-4`"))?;
+    let range = (
+        // Use map_err() to convert the error from parse() to our own error type.
+        lo.parse::<u32>().map_err(|_| ParseError::at(input, lo, "a number"))?,
+        hi.parse::<u32>().map_err(|_| ParseError::at(input, hi, "a number"))?,
+    );
+    // The checks for containment and overlap only work if the range doesn't go backwards.
+    if range.0 > range.1 {
+        return Err(ParseError::at(input, hi, format!("a number of at least {}", range.0)));
+    }
+    Ok(range)
+}
+
+fn parse_line(input: &str, line: &str) -> Result<Pair, ParseError> {
+    let (s1, s2) = line
+        .split_once(',')
+        .ok_or_else(|| ParseError::at(input, line, "a pair of ranges like `2-4,6-8`"))?;
+    let r1 = parse_range(input, s1)?;
+    let r2 = parse_range(input, s2)?;
+    Ok((r1, r2))
+}
+
+// Whether one of the ranges contains the other one.
+fn contains((r1, r2): &Pair) -> bool {
+    (r1.0 >= r2.0 && r1.1 <= r2.1) || (r2.0 >= r1.0 && r2.1 <= r1.1)
+}
+
+// Ned Batchelder has written a nice explanation of this:
+// https://nedbatchelder.com/blog/201310/range_overlap_in_two_compares.html
+fn overlaps((r1, r2): &Pair) -> bool {
+    (r1.0 <= r2.1) && (r1.1 >= r2.0)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Pair>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        input.lines().map(|line| parse_line(input, line)).collect()
+    }
+
+    fn part1(pairs: &Self::Input) -> u64 {
+        pairs.iter().filter(|pair| contains(pair)).count() as u64
+    }
+
+    fn part2(pairs: &Self::Input) -> u64 {
+        pairs.iter().filter(|pair| overlaps(pair)).count() as u64
+    }
+}
+
+// Both parts only look at one pair at a time, so we can count them while reading. A few gigabytes
+// of pairs can be more than `u32::MAX`, so the counts are u64s.
+impl StreamSolution for Day4 {
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(u64, u64), StreamError> {
+        let mut lines = LineReader::new(reader);
+        let mut counts = (0, 0);
+        while let Some((number, line)) = lines.next_line()? {
+            let pair = parse_line(line, line).map_err(|err| err.on_line(number))?;
+            counts.0 += u64::from(contains(&pair));
+            counts.1 += u64::from(overlaps(&pair));
+        }
+        Ok(counts)
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day4::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day4::part1(&input), 2);
+    assert_eq!(Day4::part2(&input), 4);
+}
+
+// The property tests found this one: with a backwards range a pair could be contained, but not
+// overlap.
+#[test]
+fn test_backwards_range() {
+    let err = Day4::parse("5-2,3-4\n").unwrap_err();
+    assert_eq!((err.column, err.expected.as_str()), (3, "a number of at least 5"));
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day4::parse(INPUT).unwrap();
+    assert_eq!(Day4::part1(&input), 503);
+    assert_eq!(Day4::part2(&input), 827);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day4>(INPUT);
+}
+
+// Reading the input bit by bit gives the same answers as parsing all of it first.
+#[test]
+fn test_stream() {
+    for input in [include_str!("../example.txt"), INPUT] {
+        let parsed = Day4::parse(input).unwrap();
+        let expected = (Day4::part1(&parsed), Day4::part2(&parsed));
+        assert_eq!(Day4::solve_stream(input.as_bytes()).unwrap(), expected);
+    }
+    match Day4::solve_stream("2-4,6-8\n2-3,5-4\n".as_bytes()) {
+        Err(StreamError::Parse(err)) => assert_eq!((err.line, err.column), (2, 7)),
+        _ => panic!("expected a parse error"),
+    }
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random pairs of ranges, some of them contained in or overlapping with each other.
+#[cfg(test)]
+fn arb_pairs() -> impl Strategy<Value = Vec<Pair>> {
+    let range = (1..100u32, 0..20u32).prop_map(|(lo, len)| (lo, lo + len));
+    proptest::collection::vec((range.clone(), range), 0..50)
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties(pairs in arb_pairs()) {
+        let input: String = pairs
+            .iter()
+            .map(|((a, b), (c, d))| format!("{a}-{b},{c}-{d}\n"))
+            .collect();
+        let parsed = Day4::parse(&input).unwrap();
+        prop_assert_eq!(&parsed, &pairs);
+        // A range that contains the other one also overlaps with it.
+        let part1 = Day4::part1(&parsed);
+        let part2 = Day4::part2(&parsed);
+        prop_assert!(part2 >= part1);
+        prop_assert!(part2 <= pairs.len() as u64);
+        prop_assert_eq!(Day4::solve_stream(input.as_bytes()).unwrap(), (part1, part2));
+        // Swapping the ranges doesn't change anything.
+        let swapped: Vec<Pair> = pairs.iter().map(|&(r1, r2)| (r2, r1)).collect();
+        prop_assert_eq!(Day4::part1(&swapped), part1);
+        prop_assert_eq!(Day4::part2(&swapped), part2);
+    }
+}
@@ -0,0 +1,208 @@
+use common::{ParseError, Solution};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub type Stack = Vec<char>;
+
+#[derive(Debug, Clone)]
+pub struct Move {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+fn read_input(input: &str) -> Result<(Vec<Stack>, Vec<Move>), ParseError> {
+    let mut stack_lines: Vec<&str> = input.lines().take_while(|l| !l.is_empty()).collect();
+    let moves_lines: Vec<&str> = input.lines().skip_while(|l| !l.is_empty()).collect();
+
+    let n_stacks = stack_lines
+        .pop()
+        .ok_or_else(|| ParseError::at(input, input, "a drawing of the stacks"))?
+        .split_whitespace()
+        .count();
+
+    let mut stacks = Vec::new();
+    for _ in 0..n_stacks {
+        stacks.push(Stack::new());
+    }
+    for line in stack_lines {
+        let chars: Vec<char> = line.chars().collect();
+        for (s, stack) in stacks.iter_mut().enumerate() {
+            // Lines can be shorter if the stacks on the right are lower (when the editor removed
+            // the trailing spaces), so use get() instead of `chars[pos]`.
+            let pos = 1 + s * 4;
+            match chars.get(pos) {
+                Some(' ') | None => {}
+                Some(&c) => stack.insert(0, c),
+            }
+        }
+    }
+    // Both parts move the same number of crates from and to the same stacks, so we can check here
+    // that there are always enough crates to move.
+    let mut heights: Vec<usize> = stacks.iter().map(|s| s.len()).collect();
+
+    let mut moves = Vec::new();
+    for line in moves_lines {
+        if line.is_empty() {
+            continue;
+        }
+        let items: Vec<&str> = line.split_whitespace().collect();
+        if items.len() != 6 || items[0] != "move" || items[2] != "from" || items[4] != "to" {
+            return Err(ParseError::at(input, line, "a move like `move 1 from 2 to 3`"));
+        }
+        let count = items[1]
+            .parse()
+            .map_err(|_| ParseError::at(input, items[1], "a number of crates"))?;
+        // Stacks are numbered starting from 1.
+        let stack = |s: &str| match s.parse() {
+            Ok(n) if n >= 1 && n <= n_stacks => Ok(n),
+            _ => Err(ParseError::at(
+                input,
+                s,
+                format!("a stack number between 1 and {n_stacks}"),
+            )),
+        };
+        let m = Move {
+            count,
+            from: stack(items[3])?,
+            to: stack(items[5])?,
+        };
+        if m.count > heights[m.from - 1] {
+            let height = heights[m.from - 1];
+            return Err(ParseError::at(input, items[1], format!("at most {height} crates")));
+        }
+        heights[m.from - 1] -= m.count;
+        heights[m.to - 1] += m.count;
+        moves.push(m);
+    }
+    Ok((stacks, moves))
+}
+
+// Empty stacks have nothing on top, so they're left out.
+fn top_crates(stacks: &[Stack]) -> String {
+    stacks.iter().filter_map(|s| s.last()).collect()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Vec<Stack>, Vec<Move>);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        let (mut stacks, moves) = input.clone();
+        for m in moves {
+            for _ in 0..m.count {
+                let item: char = stacks[m.from - 1].pop().unwrap();
+                stacks[m.to - 1].push(item);
+            }
+        }
+
+        top_crates(&stacks)
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        let (mut stacks, moves) = input.clone();
+        for m in moves {
+            let mut crates = Vec::new();
+            for _ in 0..m.count {
+                crates.insert(0, stacks[m.from - 1].pop().unwrap());
+            }
+            for c in crates {
+                stacks[m.to - 1].push(c);
+            }
+        }
+
+        top_crates(&stacks)
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day5::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day5::part1(&input), "CMZ");
+    assert_eq!(Day5::part2(&input), "MCD");
+}
+
+// Editors like to remove trailing whitespace, which used to crash the parser.
+#[test]
+fn test_short_lines() {
+    let input = "[A]\n[B] [C]\n 1   2\n\nmove 1 from 2 to 1\n";
+    let input = Day5::parse(input).unwrap();
+    // Stack 2 is empty at the end, so it doesn't show up in the answer.
+    assert_eq!(Day5::part1(&input), "C");
+    assert_eq!(Day5::part2(&input), "C");
+    let err = Day5::parse("[A]\n 1\n\nmove 2 from 1 to 1\n").unwrap_err();
+    assert_eq!((err.line, err.expected.as_str()), (4, "at most 1 crates"));
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day5::parse(INPUT).unwrap();
+    assert_eq!(Day5::part1(&input), "HNSNMTLHQ");
+    assert_eq!(Day5::part2(&input), "RNLFDJMCT");
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day5>(INPUT);
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random stacks of crates, and moves that never take more crates than there are. The lines of
+// the drawing are sometimes cut off after the last crate.
+#[cfg(test)]
+fn arb_input() -> impl Strategy<Value = String> {
+    use proptest::collection::vec;
+    let stacks = vec(vec(proptest::char::range('A', 'Z'), 0..6), 1..10);
+    let moves = vec((any::<usize>(), any::<usize>(), 0..10usize), 0..30);
+    (stacks, moves, any::<bool>()).prop_map(|(stacks, moves, trim)| {
+        let mut input = String::new();
+        let height = stacks.iter().map(|s| s.len()).max().unwrap();
+        for y in (0..height).rev() {
+            let line: Vec<String> = stacks
+                .iter()
+                .map(|s| s.get(y).map_or("   ".to_string(), |c| format!("[{c}]")))
+                .collect();
+            let line = line.join(" ");
+            input += if trim { line.trim_end() } else { &line };
+            input += "\n";
+        }
+        let numbers: Vec<String> = (1..=stacks.len()).map(|n| format!(" {n} ")).collect();
+        input += &numbers.join(" ");
+        input += "\n\n";
+        let mut heights: Vec<usize> = stacks.iter().map(|s| s.len()).collect();
+        for (from, to, count) in moves {
+            let (from, to) = (from % stacks.len(), to % stacks.len());
+            let count = count.min(heights[from]);
+            heights[from] -= count;
+            heights[to] += count;
+            input += &format!("move {count} from {} to {}\n", from + 1, to + 1);
+        }
+        input
+    })
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties(input in arb_input()) {
+        let input = Day5::parse(&input).unwrap();
+        let part1 = Day5::part1(&input);
+        let part2 = Day5::part2(&input);
+        // The crates end up on the same stacks in both parts, only their order can differ.
+        prop_assert_eq!(part1.len(), part2.len());
+        // Moving one crate at a time is the same for both cranes.
+        if input.1.iter().all(|m| m.count <= 1) {
+            prop_assert_eq!(part1, part2);
+        }
+    }
+}
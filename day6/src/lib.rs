@@ -0,0 +1,219 @@
+use std::io::BufRead;
+
+use common::{ParseError, Solution, StreamError, StreamSolution};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+// Returns the number of characters up to and including the first `wsize` different ones.
+// This used to slice the string with `&input[i..i + 1]`, which crashed on inputs shorter than the
+// window (`input.len() - wsize - 1` underflowed) and on characters longer than one byte. Working
+// on bytes and using windows() avoids both.
+fn get_start_pos(input: &str, wsize: usize) -> Option<usize> {
+    input
+        .as_bytes()
+        .windows(wsize)
+        .position(all_different)
+        .map(|i| i + wsize)
+}
+
+fn all_different(window: &[u8]) -> bool {
+    window
+        .iter()
+        .enumerate()
+        .all(|(i, c)| !window[i + 1..].contains(c))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        let signal = input.trim_end();
+        if let Some((i, c)) = signal.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            let found = &signal[i..i + c.len_utf8()];
+            return Err(ParseError::at(input, found, "a letter from `a` to `z`"));
+        }
+        // If there are 14 different characters in a row, there are also 4, so both parts have
+        // an answer.
+        if get_start_pos(signal, 14).is_none() {
+            let end = &input[signal.len()..signal.len()];
+            return Err(ParseError::at(input, end, "a marker of 14 different characters"));
+        }
+        Ok(signal.to_string())
+    }
+
+    // parse() made sure that there are markers, so the unwrap()s can't fail.
+    fn part1(input: &Self::Input) -> usize {
+        get_start_pos(input, 4).unwrap()
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        get_start_pos(input, 14).unwrap()
+    }
+}
+
+// The signal is a single line, so reading it line by line wouldn't help. Instead we read one
+// character at a time, and stop at the start-of-message marker: the rest of the signal doesn't
+// change the answers, so we don't check it either. We only keep the last 14 characters (all a
+// marker needs) and count the others, however long the signal gets before the marker. The errors
+// then show only the end of the line (see `ParseError::from_column()`).
+impl StreamSolution for Day6 {
+    fn solve_stream<R: BufRead>(mut reader: R) -> Result<(usize, usize), StreamError> {
+        const BOM: &[u8] = "\u{feff}".as_bytes();
+        if reader.fill_buf()?.starts_with(BOM) {
+            reader.consume(BOM.len());
+        }
+        let mut last = Vec::with_capacity(14);
+        // The number of characters so far, including the ones in `last`.
+        let mut count = 0;
+        let mut part1 = None;
+        for byte in reader.bytes() {
+            let byte = byte?;
+            // Whitespace (like the newline) ends the signal, like the end of the input.
+            if byte.is_ascii_whitespace() {
+                break;
+            }
+            // Not a letter: anything that's not ASCII becomes a `�`, we don't decode UTF-8 here.
+            if !byte.is_ascii_lowercase() {
+                let mut text = String::from_utf8_lossy(&last).into_owned();
+                text.push_str(&String::from_utf8_lossy(&[byte]));
+                let found = &text[last.len()..];
+                let err = ParseError::at(&text, found, "a letter from `a` to `z`");
+                // The characters before `last` were all letters, one per column.
+                return Err(err.from_column(count + 1 - last.len()).into());
+            }
+            if last.len() == 14 {
+                last.remove(0);
+            }
+            last.push(byte);
+            count += 1;
+            if part1.is_none() && last.len() >= 4 && all_different(&last[last.len() - 4..]) {
+                part1 = Some(count);
+            }
+            if last.len() == 14 && all_different(&last) {
+                // The marker of 14 different characters has one of 4 as well.
+                return Ok((part1.unwrap(), count));
+            }
+        }
+        let text = String::from_utf8_lossy(&last);
+        let end = &text[text.len()..];
+        let err = ParseError::at(&text, end, "a marker of 14 different characters");
+        Err(err.from_column(count + 1 - last.len()).into())
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day6::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day6::part1(&input), 7);
+    assert_eq!(Day6::part2(&input), 19);
+}
+
+#[test]
+fn test_more_examples() {
+    let examples = [
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+    for (example, part1, part2) in examples {
+        let input = Day6::parse(example).unwrap();
+        assert_eq!(Day6::part1(&input), part1, "{example}");
+        assert_eq!(Day6::part2(&input), part2, "{example}");
+    }
+}
+
+#[test]
+fn test_no_marker() {
+    let err = Day6::parse("abcabc\n").unwrap_err();
+    assert_eq!((err.column, err.expected.as_str()), (7, "a marker of 14 different characters"));
+    assert!(Day6::parse("").is_err());
+    assert!(Day6::parse("abcdefghijklmnö").is_err());
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day6::parse(INPUT).unwrap();
+    assert_eq!(Day6::part1(&input), 1480);
+    assert_eq!(Day6::part2(&input), 2746);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day6>(INPUT);
+}
+
+// Reading the input bit by bit gives the same answers as parsing all of it first.
+#[test]
+fn test_stream() {
+    for input in [include_str!("../example.txt"), INPUT] {
+        let parsed = Day6::parse(input).unwrap();
+        let expected = (Day6::part1(&parsed), Day6::part2(&parsed));
+        assert_eq!(Day6::solve_stream(input.as_bytes()).unwrap(), expected);
+    }
+    for input in ["abcd\n", "abcdefgh1", "abc\u{e9}"] {
+        assert!(Day6::solve_stream(input.as_bytes()).is_err(), "{input}");
+    }
+}
+
+// The stream only keeps the end of a long signal, but its errors are in the same place as those of
+// `parse()`.
+#[test]
+fn test_stream_errors() {
+    let long = "ab".repeat(1000);
+    for input in [format!("{long}1\n"), format!("{long}\n"), "abc\u{e9}".to_string()] {
+        let expected = Day6::parse(&input).unwrap_err();
+        let err = match Day6::solve_stream(input.as_bytes()) {
+            Err(StreamError::Parse(err)) => err,
+            _ => panic!("no parse error for {input:?}"),
+        };
+        assert_eq!((err.line, err.column), (expected.line, expected.column));
+        // The stream doesn't decode UTF-8, so the `é` is a `�`.
+        assert_eq!(err.found, expected.found.replace('\u{e9}', "\u{fffd}"));
+        assert_eq!(err.expected, expected.expected);
+        assert!(err.text.len() <= 15);
+    }
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random signals with a marker of 14 different characters somewhere. The part before it uses only a
+// few letters, so there are lots of repeated characters.
+#[cfg(test)]
+fn arb_signal() -> impl Strategy<Value = String> {
+    let letters: Vec<char> = ('a'..='z').collect();
+    let marker = proptest::sample::subsequence(letters, 14).prop_shuffle();
+    ("[a-e]{0,50}", marker, "[a-z]{0,50}")
+        .prop_map(|(before, marker, after)| {
+            format!("{before}{}{after}\n", String::from_iter(marker))
+        })
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties(signal in arb_signal()) {
+        let input = Day6::parse(&signal).unwrap();
+        let part1 = Day6::part1(&input);
+        let part2 = Day6::part2(&input);
+        prop_assert_eq!(Day6::solve_stream(signal.as_bytes()).unwrap(), (part1, part2));
+        // The first 14 different characters contain 4 different characters.
+        prop_assert!(part1 >= 4 && part1 <= part2 - 10);
+        let marker: std::collections::HashSet<char> = input[part2 - 14..part2].chars().collect();
+        prop_assert_eq!(marker.len(), 14);
+    }
+
+    // Anything at all is either rejected or solved, but never crashes.
+    #[test]
+    fn test_any_input(signal in ".{0,30}") {
+        if let Ok(input) = Day6::parse(&signal) {
+            prop_assert!(Day6::part1(&input) <= Day6::part2(&input));
+        }
+    }
+}
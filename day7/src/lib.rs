@@ -0,0 +1,322 @@
+// This problem took way longer than all of the previous ones, because I had to learn so much about
+// the rust borrow checker. Getting the tree data structure right was surprisingly complicated.
+//
+// Here is what I learned:
+//
+// - You can't have a tree where the nodes have children as well as a reference back to the parent.
+// - One of the ways to work around this is to store references to the nodes in an external data
+//   structure, like a HashMap.
+// - You need `Rc` (reference counted pointers) if you want to store data in more than one
+//   place (e.g. nodes are stored in the tree as well as in the hash map).
+// - You can not get a value "out" of an `Rc`!
+// - `Rc`s are always immutable. If you want to mutate the wrapped object you need `RefCell`.
+// - `RefCell` allows to mutate an object even while immutable references exist. Wrapping an
+//   object in a `RefCell` means the ownership rules are checked at runtime instead of at compile
+//   time: Violating them will cause a runtime error.
+//
+// There are still some uses of `unwrap()` in functions other than `main`, which I'd like to avoid.
+//
+// I had to convert between `&str` and `String` a lot (`.as_str()`, `.to_string()`). I wonder if
+// there is a better way to do this.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use common::{ParseError, Solution};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+#[derive(Debug)]
+enum Command {
+    Ls,
+    Cd(String),
+}
+
+// The file name is never used for the puzzle, but it's nice to see when debugging.
+#[allow(dead_code)]
+#[derive(Debug)]
+enum Entry {
+    Dir(String),
+    File(String, u64),
+}
+
+#[derive(Debug)]
+enum Line {
+    Command(Command),
+    Entry(Entry),
+}
+
+fn read_input(input: &str) -> Result<Vec<Line>, ParseError> {
+    let mut result = Vec::new();
+    for line in input.lines() {
+        if let Some(line) = line.strip_prefix("$ ") {
+            let cmd = parse_command(input, line)?;
+            result.push(Line::Command(cmd));
+        } else {
+            let entry = parse_entry(input, line)?;
+            result.push(Line::Entry(entry));
+        }
+    }
+    Ok(result)
+}
+
+// Like in day 4, the parsers take the whole input as well, so errors can point at the right line.
+fn parse_command(input: &str, line: &str) -> Result<Command, ParseError> {
+    if line.starts_with("ls") {
+        Ok(Command::Ls)
+    } else {
+        line.strip_prefix("cd ")
+            .map(|name| Command::Cd(name.to_string()))
+            .ok_or_else(|| ParseError::at(input, line, "a command (`ls` or `cd <dir>`)"))
+    }
+}
+
+fn parse_entry(input: &str, line: &str) -> Result<Entry, ParseError> {
+    if let Some(name) = line.strip_prefix("dir ") {
+        Ok(Entry::Dir(name.to_string()))
+    } else {
+        let (size, name) = line.split_once(' ').ok_or_else(|| {
+            ParseError::at(input, line, "a directory entry like `dir a` or `1234 b.txt`")
+        })?;
+        let size = size
+            .parse()
+            .map_err(|_| ParseError::at(input, size, "a file size"))?;
+        Ok(Entry::File(name.to_string(), size))
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    size: u64,
+    children: RefCell<Vec<Rc<Node>>>,
+}
+
+#[derive(Debug)]
+pub struct Tree {
+    root: Rc<Node>,
+    lookup: HashMap<String, Rc<Node>>,
+}
+
+fn build_tree(lines: Vec<Line>) -> Tree {
+    let mut tree = Tree {
+        root: Rc::new(Node {
+            size: 0,
+            children: RefCell::new(vec![]),
+        }),
+        lookup: HashMap::new(),
+    };
+    tree.lookup.insert("".to_string(), Rc::clone(&tree.root));
+    let mut cwd = "".to_string();
+    for line in lines {
+        match line {
+            Line::Command(Command::Ls) => {}
+            Line::Command(Command::Cd(name)) => match name.as_str() {
+                "/" => cwd = "".to_string(),
+                ".." => {
+                    cwd = cwd
+                        .rsplit_once('/')
+                        .unwrap_or((cwd.as_str(), ""))
+                        .0
+                        .to_string()
+                }
+                name => {
+                    let path = format!("{cwd}/{name}");
+                    // We can `cd` into a directory that wasn't listed before, so create it if it
+                    // doesn't exist yet (like `mkdir -p`). The unwrap()s below rely on it.
+                    if !tree.lookup.contains_key(&path) {
+                        let node = Rc::new(Node {
+                            size: 0,
+                            children: RefCell::new(vec![]),
+                        });
+                        let parent = tree.lookup.get(cwd.as_str()).unwrap();
+                        parent.children.borrow_mut().push(Rc::clone(&node));
+                        tree.lookup.insert(path.clone(), node);
+                    }
+                    cwd = path
+                }
+            },
+            Line::Entry(Entry::Dir(name)) => {
+                let path = format!("{cwd}/{name}");
+                let parent = tree.lookup.get_mut(cwd.as_str()).unwrap();
+                let node = Rc::new(Node {
+                    size: 0,
+                    children: RefCell::new(vec![]),
+                });
+                // TODO Why does this work only in exactly this order (push, then insert)?
+                // Doing insert then push complains about tree.lookup being borrowed twice, why?
+                //tree.lookup.insert(path, Rc::clone(&node));
+                parent.children.borrow_mut().push(Rc::clone(&node));
+                // TODO why does this work without Rc::clone()?
+                tree.lookup.insert(path, node);
+            }
+            Line::Entry(Entry::File(_, size)) => {
+                let parent = tree.lookup.get_mut(cwd.as_str()).unwrap();
+                let entry = Node {
+                    size,
+                    children: RefCell::new(vec![]),
+                };
+                parent.children.borrow_mut().push(Rc::new(entry));
+            }
+        }
+    }
+    tree
+}
+
+fn get_size(node: &Rc<Node>) -> u64 {
+    let mut total = 0;
+    // This is how you iterate over a RefCell<Vec<...>>
+    for child in node.children.borrow().iter() {
+        total += child.size;
+        total += get_size(child);
+    }
+    total
+}
+
+fn get_size_at(tree: &Tree, path: &str) -> Option<u64> {
+    let node = match path {
+        "/" => &tree.root,
+        path => tree.lookup.get(path)?,
+    };
+    Some(get_size(node))
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Tree;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        let lines = read_input(input)?;
+        Ok(build_tree(lines))
+    }
+
+    fn part1(tree: &Self::Input) -> u64 {
+        let mut result = 0;
+        for dir in tree.lookup.keys() {
+            let size = get_size_at(tree, dir.as_str()).unwrap();
+            if size <= 100000 {
+                result += size;
+            }
+        }
+        result
+    }
+
+    fn part2(tree: &Self::Input) -> u64 {
+        let space_used = get_size(&tree.root);
+        // saturating_sub() stops at 0 instead of underflowing, for disks that are fuller than
+        // the disk can be, or that already have enough space.
+        let space_available = 70000000u64.saturating_sub(space_used);
+        let min_size = 30000000u64.saturating_sub(space_available);
+        let mut result = space_used;
+        for dir in tree.lookup.keys() {
+            let size = get_size_at(tree, dir.as_str()).unwrap();
+            if size >= min_size && size < result {
+                result = size;
+            }
+        }
+        result
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day7::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day7::part1(&input), 95437);
+    assert_eq!(Day7::part2(&input), 24933642);
+}
+
+#[test]
+fn test_cd_without_ls() {
+    let tree = Day7::parse("$ cd a\n$ cd b\n$ ls\n100 x\n$ cd /\n$ ls\n5 y\n").unwrap();
+    // The directories /a/b, /a and / all count for part 1.
+    assert_eq!(Day7::part1(&tree), 100 + 100 + 105);
+    // There's plenty of space already, so deleting the smallest directory is enough.
+    assert_eq!(Day7::part2(&tree), 100);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day7::parse(INPUT).unwrap();
+    assert_eq!(Day7::part1(&input), 1307902);
+    assert_eq!(Day7::part2(&input), 7068748);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day7>(INPUT);
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+#[cfg(test)]
+#[derive(Debug, Clone)]
+struct Dir {
+    files: Vec<u64>,
+    dirs: Vec<Dir>,
+}
+
+#[cfg(test)]
+impl Dir {
+    fn size(&self) -> u64 {
+        self.files.iter().sum::<u64>() + self.dirs.iter().map(|d| d.size()).sum::<u64>()
+    }
+
+    // The sizes of this directory and all directories below it.
+    fn all_sizes(&self) -> Vec<u64> {
+        let mut sizes = vec![self.size()];
+        for dir in &self.dirs {
+            sizes.extend(dir.all_sizes());
+        }
+        sizes
+    }
+
+    // What the shell session looks like when we look at every directory, depth first.
+    fn transcript(&self, out: &mut String) {
+        *out += "$ ls\n";
+        for (i, _) in self.dirs.iter().enumerate() {
+            *out += &format!("dir d{i}\n");
+        }
+        for (i, size) in self.files.iter().enumerate() {
+            *out += &format!("{size} f{i}.txt\n");
+        }
+        for (i, dir) in self.dirs.iter().enumerate() {
+            *out += &format!("$ cd d{i}\n");
+            dir.transcript(out);
+            *out += "$ cd ..\n";
+        }
+    }
+}
+
+// Random directory trees, up to 4 levels deep. Some of them are larger than the disk.
+#[cfg(test)]
+fn arb_dir() -> impl Strategy<Value = Dir> {
+    use proptest::collection::vec;
+    let leaf = vec(1..20_000_000u64, 0..4).prop_map(|files| Dir { files, dirs: vec![] });
+    leaf.prop_recursive(4, 30, 4, |inner| {
+        (vec(1..20_000_000u64, 0..4), vec(inner, 0..4))
+            .prop_map(|(files, dirs)| Dir { files, dirs })
+    })
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties(root in arb_dir()) {
+        let mut transcript = "$ cd /\n".to_string();
+        root.transcript(&mut transcript);
+        let tree = Day7::parse(&transcript).unwrap();
+
+        let sizes = root.all_sizes();
+        let part1: u64 = sizes.iter().filter(|&&size| size <= 100000).sum();
+        prop_assert_eq!(Day7::part1(&tree), part1);
+
+        let needed = 30000000u64.saturating_sub(70000000u64.saturating_sub(root.size()));
+        let part2 = sizes.iter().copied().filter(|&size| size >= needed).min().unwrap();
+        prop_assert_eq!(Day7::part2(&tree), part2);
+    }
+}
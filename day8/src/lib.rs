@@ -0,0 +1,138 @@
+use common::{ParseError, Solution};
+use grid::{Grid, Point};
+use viz::{Image, Rgb};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub type Map = Grid<u8>;
+
+fn read_input(input: &str) -> Result<Map, ParseError> {
+    Grid::parse(input, "a tree height", |_, c| {
+        c.to_digit(10).map(|h| h as u8)
+    })
+}
+
+// A tree is visible if all trees between it and the edge are lower, in at least one direction.
+// For the trees on the edge there are no trees in between, so they are always visible.
+fn is_visible(map: &Map, p: Point) -> bool {
+    let height = map[p];
+    Point::DIRECTIONS4
+        .iter()
+        .any(|&d| map.ray(p, d).all(|(_, &other)| other < height))
+}
+
+fn count_visible_trees(map: &Map) -> usize {
+    map.points().filter(|&p| is_visible(map, p)).count()
+}
+
+fn get_score(map: &Map, p: Point) -> u32 {
+    let height = map[p];
+    // In every direction, count the trees up to (and including) the first one that blocks the
+    // view. Then multiply the counts for all directions.
+    Point::DIRECTIONS4
+        .iter()
+        .map(|&d| {
+            let mut score = 0;
+            for (_, &other) in map.ray(p, d) {
+                score += 1;
+                if other >= height {
+                    break;
+                }
+            }
+            score
+        })
+        .product()
+}
+
+fn get_best_scenic_score(map: &Map) -> u32 {
+    map.points().map(|p| get_score(map, p)).max().unwrap_or(0)
+}
+
+// One pixel per tree, the higher the tree the lighter the green.
+pub fn image(map: &Map) -> Image {
+    let (low, high) = (Rgb(10, 40, 10), Rgb(150, 230, 110));
+    Image::from_fn(map.width() as usize, map.height() as usize, |x, y| {
+        let height = map[Point::new(x as i32, y as i32)];
+        low.mix(high, height as f64 / 9.0)
+    })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Map;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(map: &Self::Input) -> usize {
+        count_visible_trees(map)
+    }
+
+    fn part2(map: &Self::Input) -> u32 {
+        get_best_scenic_score(map)
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day8::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day8::part1(&input), 21);
+    assert_eq!(Day8::part2(&input), 8);
+}
+
+// The picture of the example, saved with `aoc image 8 day8/example.ppm --input day8/example.txt`.
+#[test]
+fn test_image() {
+    let input = Day8::parse(include_str!("../example.txt")).unwrap();
+    let expected = Image::from_ppm(include_bytes!("../example.ppm")).unwrap();
+    assert_eq!(image(&input), expected);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day8::parse(INPUT).unwrap();
+    assert_eq!(Day8::part1(&input), 1798);
+    assert_eq!(Day8::part2(&input), 259308);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day8>(INPUT);
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random forests of 1x1 up to 12x12 trees.
+#[cfg(test)]
+fn arb_forest() -> impl Strategy<Value = (usize, usize, String)> {
+    (1..=12usize, 1..=12usize).prop_flat_map(|(w, h)| {
+        let row = proptest::collection::vec(0..=9u8, w)
+            .prop_map(|row| row.iter().map(|h| format!("{h}")).collect::<String>() + "\n");
+        proptest::collection::vec(row, h).prop_map(move |rows| (w, h, rows.concat()))
+    })
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties((w, h, forest) in arb_forest()) {
+        let map = Day8::parse(&forest).unwrap();
+        // All trees on the edge are visible, and no tree is visible twice.
+        let inner = w.saturating_sub(2) * h.saturating_sub(2);
+        let visible = Day8::part1(&map);
+        prop_assert!(visible >= w * h - inner && visible <= w * h);
+        // A tree can't see more than the whole row and column, and trees on the edge see nothing
+        // in at least one direction.
+        let best = Day8::part2(&map);
+        prop_assert!(best <= ((w - 1) * (w - 1) * (h - 1) * (h - 1)) as u32);
+        if w <= 2 || h <= 2 {
+            prop_assert_eq!(best, 0);
+        }
+    }
+}
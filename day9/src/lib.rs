@@ -0,0 +1,266 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::io::BufRead;
+
+use common::{LineReader, ParseError, Solution, StreamError, StreamSolution};
+use viz::{Frame, Style};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+#[derive(Debug)]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+#[derive(Debug)]
+pub struct Move {
+    dir: Direction,
+    steps: u8,
+}
+
+#[derive(Debug)]
+struct Point {
+    // i16 overflowed after about 130 long moves in the same direction, i32 doesn't.
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug)]
+struct Board {
+    rope: Vec<Point>,
+    seen: HashSet<(i32, i32)>,
+}
+
+impl Board {
+    fn new(len: usize) -> Self {
+        let mut rope = vec![];
+        for _ in 0..len {
+            rope.push(Point { x: 0, y: 0 });
+        }
+        let mut seen = HashSet::new();
+        seen.insert((0, 0));
+
+        Self { rope, seen }
+    }
+
+    fn move_head(&mut self, m: &Move) {
+        //println!("== {:?} {} ==", m.dir, m.steps);
+        for _ in 0..m.steps {
+            match m.dir {
+                Direction::Up => self.rope[0].y += 1,
+                Direction::Right => self.rope[0].x += 1,
+                Direction::Down => self.rope[0].y -= 1,
+                Direction::Left => self.rope[0].x -= 1,
+            }
+            //println!("{}", self);
+            self.move_tail();
+        }
+    }
+
+    fn move_tail(&mut self) {
+        for i in 1..self.rope.len() {
+            let dx = self.rope[i - 1].x - self.rope[i].x;
+            let dy = self.rope[i - 1].y - self.rope[i].y;
+            if dx.abs() > 1 || dy.abs() > 1 {
+                self.rope[i].x += dx.signum();
+                self.rope[i].y += dy.signum();
+                if i == self.rope.len() - 1 {
+                    self.seen.insert((self.rope[i].x, self.rope[i].y));
+                }
+                //println!("{}", self);
+            }
+        }
+    }
+}
+
+impl Board {
+    // Draws the rope like the puzzle text does: `H` for the head, then the knots `1` to `9` (or
+    // `T` if there is only one), `s` for the start and `#` for the places the tail has been to.
+    // Up is positive `y`, so the rows go from the top down.
+    fn frame(&self) -> Frame {
+        let knots = self.rope.iter().map(|p| (p.x, p.y));
+        let all: Vec<(i32, i32)> = knots.chain(self.seen.iter().copied()).collect();
+        let min_x = all.iter().map(|p| p.0).min().unwrap_or(0);
+        let max_x = all.iter().map(|p| p.0).max().unwrap_or(0);
+        let min_y = all.iter().map(|p| p.1).min().unwrap_or(0);
+        let max_y = all.iter().map(|p| p.1).max().unwrap_or(0);
+
+        let mut frame = Frame::new();
+        for y in (min_y..=max_y).rev() {
+            for x in min_x..=max_x {
+                // The first knot at a position covers the ones behind it.
+                let knot = self.rope.iter().position(|p| p.x == x && p.y == y);
+                match knot {
+                    Some(0) => frame.push('H', Style::Highlight),
+                    Some(_) if self.rope.len() == 2 => frame.push('T', Style::Bright),
+                    Some(i) => frame.push_str(&i.to_string(), Style::Bright),
+                    None if (x, y) == (0, 0) => frame.push('s', Style::Plain),
+                    None if self.seen.contains(&(x, y)) => frame.push('#', Style::Dim),
+                    None => frame.push('.', Style::Dim),
+                }
+            }
+            frame.newline();
+        }
+        frame
+    }
+}
+
+// https://doc.rust-lang.org/std/fmt/trait.Display.html
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.frame())
+    }
+}
+
+fn parse_move(input: &str, line: &str) -> Result<Move, ParseError> {
+    let (direction, steps) = line
+        .split_once(' ')
+        .ok_or_else(|| ParseError::at(input, line, "a move like `R 4`"))?;
+    let dir = match direction {
+        "U" => Direction::Up,
+        "R" => Direction::Right,
+        "D" => Direction::Down,
+        "L" => Direction::Left,
+        _ => return Err(ParseError::at(input, direction, "one of U, R, D or L")),
+    };
+    Ok(Move {
+        dir,
+        steps: steps
+            .parse()
+            .map_err(|_| ParseError::at(input, steps, "a number of steps"))?,
+    })
+}
+
+fn read_input(input: &str) -> Result<Vec<Move>, ParseError> {
+    input.lines().map(|line| parse_move(input, line)).collect()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Move>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse_normalized(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part1(moves: &Self::Input) -> usize {
+        let mut board = Board::new(2);
+        for m in moves {
+            board.move_head(m);
+        }
+        board.seen.len()
+    }
+
+    fn part2(moves: &Self::Input) -> usize {
+        let mut board = Board::new(10);
+        for m in moves {
+            board.move_head(m);
+        }
+        board.seen.len()
+    }
+}
+
+// Both ropes can follow the moves while we read them. The places the tails have been to are
+// still kept, so the memory grows with the area the rope covers, but not with the moves.
+impl StreamSolution for Day9 {
+    fn solve_stream<R: BufRead>(reader: R) -> Result<(usize, usize), StreamError> {
+        let mut lines = LineReader::new(reader);
+        let mut short = Board::new(2);
+        let mut long = Board::new(10);
+        while let Some((number, line)) = lines.next_line()? {
+            let m = parse_move(line, line).map_err(|err| err.on_line(number))?;
+            short.move_head(&m);
+            long.move_head(&m);
+        }
+        Ok((short.seen.len(), long.seen.len()))
+    }
+}
+
+#[test]
+fn test_example() {
+    let input = Day9::parse(include_str!("../example.txt")).unwrap();
+    assert_eq!(Day9::part1(&input), 13);
+    assert_eq!(Day9::part2(&input), 1);
+}
+
+#[test]
+fn test_display() {
+    let moves = Day9::parse("R 4\nU 2\n").unwrap();
+    let mut board = Board::new(2);
+    board.move_head(&moves[0]);
+    assert_eq!(board.to_string(), "s##TH\n");
+
+    let mut board = Board::new(10);
+    for m in &moves {
+        board.move_head(m);
+    }
+    // The knots behind 5 (and the start) are all covered by it.
+    assert_eq!(board.to_string(), "....H\n.4321\n5....\n");
+}
+
+// Part 2 has a second, larger example.
+#[test]
+fn test_example2() {
+    let input = Day9::parse(include_str!("../example2.txt")).unwrap();
+    assert_eq!(Day9::part2(&input), 36);
+}
+
+// Our answers for the real input, so refactorings can't change them without noticing.
+#[test]
+fn test_input() {
+    let input = Day9::parse(INPUT).unwrap();
+    assert_eq!(Day9::part1(&input), 6175);
+    assert_eq!(Day9::part2(&input), 2578);
+}
+
+#[test]
+fn test_crlf() {
+    common::test_crlf::<Day9>(INPUT);
+}
+
+// Reading the input bit by bit gives the same answers as parsing all of it first.
+#[test]
+fn test_stream() {
+    for input in [include_str!("../example.txt"), include_str!("../example2.txt"), INPUT] {
+        let parsed = Day9::parse(input).unwrap();
+        let expected = (Day9::part1(&parsed), Day9::part2(&parsed));
+        assert_eq!(Day9::solve_stream(input.as_bytes()).unwrap(), expected);
+    }
+}
+
+#[cfg(test)]
+use proptest::prelude::*;
+
+// Random moves, including long ones that take the rope far away from the start.
+#[cfg(test)]
+fn arb_moves() -> impl Strategy<Value = String> {
+    let step = (proptest::sample::select(vec!["U", "R", "D", "L"]), 0..=255u8)
+        .prop_map(|(dir, steps)| format!("{dir} {steps}\n"));
+    proptest::collection::vec(step, 0..200).prop_map(|moves| moves.concat())
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn test_properties(input in arb_moves()) {
+        let moves = Day9::parse(&input).unwrap();
+        let steps: usize = moves.iter().map(|m| m.steps as usize).sum();
+        // The tail is always at the start, and it moves at most once per step of the head.
+        for visited in [Day9::part1(&moves), Day9::part2(&moves)] {
+            prop_assert!(visited >= 1 && visited <= steps + 1);
+        }
+    }
+}
+
+#[test]
+fn test_far_away() {
+    let moves = Day9::parse(&"R 255\n".repeat(200)).unwrap();
+    assert_eq!(Day9::part1(&moves), 200 * 255);
+    assert_eq!(Day9::part2(&moves), 200 * 255 - 8);
+}
@@ -0,0 +1,247 @@
+// A grid with a value in every cell, stored row by row in a single `Vec`.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use common::ParseError;
+
+use crate::Point;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: i32,
+    height: i32,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: i32, height: i32, value: T) -> Self
+    where
+        T: Clone,
+    {
+        assert!(width >= 0 && height >= 0, "negative grid size");
+        Grid {
+            cells: vec![value; (width * height) as usize],
+            width,
+            height,
+        }
+    }
+
+    // Returns `None` if the rows don't all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            width: width as i32,
+            height: rows.len() as i32,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    // Parses a grid with one character per cell, like the maps in the puzzle inputs. `cell`
+    // converts a character (at the given position) into a value, and returns `None` if the
+    // character isn't valid. `expected` describes what a valid character would have been.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(Point, char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let mut row_width = 0;
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                let value = cell(Point::new(x as i32, y as i32), c)
+                    .ok_or_else(|| ParseError::at(input, &line[i..i + c.len_utf8()], expected))?;
+                cells.push(value);
+                row_width += 1;
+            }
+            // All rows need to be as wide as the first one.
+            let width = *width.get_or_insert(row_width);
+            if row_width == 0 || row_width != width {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("a row of {width} cells"),
+                ));
+            }
+            height += 1;
+        }
+        let width = width.ok_or_else(|| ParseError::at(input, input, "a grid"))?;
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.x < self.width && p.y >= 0 && p.y < self.height
+    }
+
+    // The one place where a point is converted into an index into the `Vec`.
+    fn index_of(&self, p: Point) -> Option<usize> {
+        self.contains(p).then(|| (p.y * self.width + p.x) as usize)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    // All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    // All cells with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: i32) -> impl Iterator<Item = (Point, &T)> {
+        self.ray(Point::new(-1, y), Point::RIGHT)
+    }
+
+    pub fn column(&self, x: i32) -> impl Iterator<Item = (Point, &T)> {
+        self.ray(Point::new(x, -1), Point::DOWN)
+    }
+
+    // The cells you see when you look from `start` in `direction`, up to the edge of the grid.
+    // `start` itself is not included, and it may be outside of the grid (just next to it), which
+    // is how `row` and `column` work.
+    pub fn ray(&self, start: Point, direction: Point) -> impl Iterator<Item = (Point, &T)> {
+        let mut p = start;
+        std::iter::from_fn(move || {
+            p += direction;
+            self.get(p).map(|value| (p, value))
+        })
+    }
+
+    // The neighbours of `p` (that are inside the grid) which share an edge with it...
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours4()
+            .filter_map(|n| self.get(n).map(|value| (n, value)))
+    }
+
+    // ...or an edge or a corner.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours8()
+            .filter_map(|n| self.get(n).map(|value| (n, value)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    // Draws the grid with one character per cell, and a newline after every row.
+    pub fn render(&self, mut f: impl FnMut(Point, &T) -> char) -> String {
+        let mut result = String::new();
+        for (p, value) in self.iter() {
+            result.push(f(p, value));
+            if p.x == self.width - 1 {
+                result.push('\n');
+            }
+        }
+        result
+    }
+}
+
+// `grid[p]` panics if `p` is outside of the grid, like indexing a `Vec`.
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        match self.get(p) {
+            Some(value) => value,
+            None => panic!("{p} is outside of the {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(p) {
+            Some(value) => value,
+            None => panic!("{p} is outside of the {width}x{height} grid"),
+        }
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(|_, &c| c))
+    }
+}
+
+#[cfg(test)]
+fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse(input, "a digit", |_, c| c.to_digit(10))
+}
+
+#[test]
+fn test_parse_and_render() {
+    let grid = digits("123\n456\n").unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Point::new(2, 1)], 6);
+    assert_eq!(grid.get(Point::new(3, 1)), None);
+    assert_eq!(grid.get(Point::new(-1, 0)), None);
+    let rendered = grid.render(|_, &d| char::from_digit(d + 1, 10).unwrap());
+    assert_eq!(rendered, "234\n567\n");
+
+    let err = digits("123\n4x6\n").unwrap_err();
+    assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "x"));
+    let err = digits("123\n45\n").unwrap_err();
+    assert_eq!((err.line, err.expected.as_str()), (2, "a row of 3 cells"));
+    assert_eq!(digits("").unwrap_err().expected, "a grid");
+}
+
+#[test]
+fn test_iterators() {
+    let grid = digits("123\n456\n789\n").unwrap();
+    let values =
+        |it: &mut dyn Iterator<Item = (Point, &u32)>| -> Vec<u32> { it.map(|(_, &v)| v).collect() };
+    assert_eq!(values(&mut grid.row(1)), [4, 5, 6]);
+    assert_eq!(values(&mut grid.column(2)), [3, 6, 9]);
+    assert_eq!(values(&mut grid.ray(Point::new(1, 1), Point::UP)), [2]);
+    assert_eq!(
+        values(&mut grid.ray(Point::new(0, 0), Point::new(1, 1))),
+        [5, 9]
+    );
+    assert_eq!(values(&mut grid.ray(Point::new(2, 2), Point::RIGHT)), []);
+    assert_eq!(values(&mut grid.neighbours4(Point::new(0, 0))), [2, 4]);
+    assert_eq!(values(&mut grid.neighbours8(Point::new(0, 0))), [2, 4, 5]);
+    assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+    assert_eq!(values(&mut grid.iter()), [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn test_from_rows() {
+    let grid = Grid::from_rows(vec![vec!['a', 'b'], vec!['c', 'd']]).unwrap();
+    assert_eq!(grid.to_string(), "ab\ncd\n");
+    assert!(Grid::from_rows(vec![vec!['a', 'b'], vec!['c']]).is_none());
+
+    let mut grid = Grid::new(2, 1, '.');
+    grid[Point::new(1, 0)] = '#';
+    assert_eq!(grid.to_string(), ".#\n");
+}
@@ -0,0 +1,19 @@
+// 2D grids, which come up in a lot of the puzzles (days 8, 12 and 14 so far).
+//
+// Coordinates are signed (`Point` has `i32` fields), so we can step off the edge of a grid and
+// just check if we're still inside, instead of converting between `usize` and `i32` everywhere.
+//
+// There are two kinds of grids:
+//
+// - `Grid` has a value for every cell and is stored in a single `Vec`. Good for maps that are
+//   read from the input, like the trees in day 8.
+// - `SparseGrid` only stores the cells that are set, in a `HashMap`. Good for things that can be
+//   anywhere and grow in any direction, like the sand in day 14.
+
+pub mod dense;
+pub mod point;
+pub mod sparse;
+
+pub use dense::Grid;
+pub use point::Point;
+pub use sparse::SparseGrid;
@@ -0,0 +1,155 @@
+// A position on a grid, or the difference between two positions (a direction).
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+// `y` grows downwards, like the lines of the puzzle input. Points are `Copy`, so they can be passed
+// around like integers. The ordering (first by `x`, then by `y`) is the same as for a tuple
+// `(x, y)`, it's needed to put points into a `BinaryHeap`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ZERO: Point = Point::new(0, 0);
+    pub const UP: Point = Point::new(0, -1);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+    pub const RIGHT: Point = Point::new(1, 0);
+
+    // The directions to the 4 neighbours that share an edge with a cell...
+    pub const DIRECTIONS4: [Point; 4] = [Point::LEFT, Point::RIGHT, Point::UP, Point::DOWN];
+
+    // ...and the 8 neighbours that share an edge or a corner.
+    pub const DIRECTIONS8: [Point; 8] = [
+        Point::new(-1, -1),
+        Point::UP,
+        Point::new(1, -1),
+        Point::LEFT,
+        Point::RIGHT,
+        Point::new(-1, 1),
+        Point::DOWN,
+        Point::new(1, 1),
+    ];
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Point::DIRECTIONS4.into_iter().map(move |d| self + d)
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Point::DIRECTIONS8.into_iter().map(move |d| self + d)
+    }
+
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // Turns a difference into a single step in the same direction, e.g. (3, -2) into (1, -1).
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i32) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+// Instead of a standalone parse_point function, it is more idiomatic to
+// implement the FromStr trait for our type.
+// We can use this later as Point::from_str("498,4") or "498,4".parse::<Point>().
+// See https://doc.rust-lang.org/std/str/trait.FromStr.html#examples
+impl FromStr for Point {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s.split_once(',').ok_or(format!("Invalid point: {s}"))?;
+        let x = x
+            .parse::<i32>()
+            .map_err(|_| format!("Invalid X coordinate: {x}"))?;
+        let y = y
+            .parse::<i32>()
+            .map_err(|_| format!("Invalid Y coordinate: {y}"))?;
+        Ok(Point { x, y })
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    let p = Point::new(3, -2);
+    assert_eq!(p + Point::RIGHT, Point::new(4, -2));
+    assert_eq!(p - Point::new(1, 1), Point::new(2, -3));
+    assert_eq!(-p, Point::new(-3, 2));
+    assert_eq!(p * 2, Point::new(6, -4));
+    assert_eq!(p.signum(), Point::new(1, -1));
+    assert_eq!(p.manhattan(Point::ZERO), 5);
+}
+
+#[test]
+fn test_neighbours() {
+    let p = Point::new(0, 0);
+    assert_eq!(p.neighbours4().count(), 4);
+    assert!(p.neighbours4().all(|n| n.manhattan(p) == 1));
+    assert_eq!(p.neighbours8().count(), 8);
+    assert!(!p.neighbours8().any(|n| n == p));
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!("498,4".parse(), Ok(Point::new(498, 4)));
+    assert_eq!("-1,2".parse(), Ok(Point::new(-1, 2)));
+    assert!("498".parse::<Point>().is_err());
+    assert!("a,4".parse::<Point>().is_err());
+}
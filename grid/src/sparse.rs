@@ -0,0 +1,114 @@
+// A grid that only stores the cells that are set. It has no fixed size, but keeps track of the
+// smallest rectangle that contains all cells (the bounds), for drawing it.
+
+use std::collections::HashMap;
+
+use crate::Point;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    // The top left and bottom right corner of the bounds, `None` while the grid is empty.
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns the previous value of the cell, like `HashMap::insert`.
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (p, p),
+            Some((min, max)) => (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            ),
+        });
+        self.cells.insert(p, value)
+    }
+
+    // The bounds are not made smaller again when cells are removed, they only ever grow.
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    // All cells that are set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, value)| (p, value))
+    }
+
+    // The neighbours of `p` that are set.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours4()
+            .filter_map(|n| self.get(n).map(|value| (n, value)))
+    }
+
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        p.neighbours8()
+            .filter_map(|n| self.get(n).map(|value| (n, value)))
+    }
+
+    // Draws everything inside the bounds, `f` also gets called for the cells that are not set.
+    pub fn render(&self, mut f: impl FnMut(Point, Option<&T>) -> char) -> String {
+        let mut result = String::new();
+        if let Some((min, max)) = self.bounds {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    let p = Point::new(x, y);
+                    result.push(f(p, self.get(p)));
+                }
+                result.push('\n');
+            }
+        }
+        result
+    }
+}
+
+#[test]
+fn test_sparse_grid() {
+    let mut grid = SparseGrid::new();
+    assert_eq!(grid.bounds(), None);
+    assert_eq!(grid.render(|_, _| '?'), "");
+
+    grid.insert(Point::new(2, -1), '#');
+    grid.insert(Point::new(-1, 1), 'o');
+    assert_eq!(grid.len(), 2);
+    assert!(grid.contains(Point::new(2, -1)));
+    assert_eq!(grid.get(Point::new(0, 0)), None);
+    assert_eq!(grid.bounds(), Some((Point::new(-1, -1), Point::new(2, 1))));
+    assert_eq!(grid.render(|_, c| *c.unwrap_or(&'.')), "...#\n....\no...\n");
+    assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 1);
+    assert_eq!(grid.remove(Point::new(-1, 1)), Some('o'));
+    assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 0);
+}
@@ -0,0 +1,57 @@
+// Writes an asciicast (version 2) recording, the format of asciinema:
+// https://docs.asciinema.org/manual/asciicast/v2/
+//
+// It's a header line with the size of the terminal, followed by one line per output event with
+// the time (in seconds since the start) and the text that was written:
+//
+//     {"version": 2, "width": 40, "height": 6}
+//     [0.0, "o", "\u001b[2J\u001b[H..."]
+//     [0.008, "o", "\u001b[H..."]
+
+use std::io::{self, Write};
+
+pub struct Recorder<W: Write> {
+    out: W,
+    width: usize,
+    height: usize,
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(mut out: W, width: usize, height: usize) -> io::Result<Self> {
+        writeln!(
+            out,
+            "{{\"version\": 2, \"width\": {width}, \"height\": {height}}}"
+        )?;
+        Ok(Recorder { out, width, height })
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    pub fn output(&mut self, time: f64, data: &str) -> io::Result<()> {
+        // serde_json takes care of escaping the string (including the escape sequences).
+        let data = serde_json::to_string(data)?;
+        // Microseconds are precise enough, and avoid times like 0.30000000000000004.
+        let time = (time * 1e6).round() / 1e6;
+        writeln!(self.out, "[{time}, \"o\", {data}]")
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+#[test]
+fn test_recorder() {
+    let mut recorder = Recorder::new(vec![], 3, 1).unwrap();
+    recorder.output(0.0, "\x1B[H#\"\n").unwrap();
+    recorder.output(0.5, "..").unwrap();
+    let recording = String::from_utf8(recorder.into_inner()).unwrap();
+    assert_eq!(
+        recording,
+        "{\"version\": 2, \"width\": 3, \"height\": 1}\n\
+         [0, \"o\", \"\\u001b[H#\\\"\\n\"]\n\
+         [0.5, \"o\", \"..\"]\n"
+    );
+}
@@ -0,0 +1,119 @@
+// A frame is a block of text where every character has a style.
+
+use std::fmt;
+
+use crate::Theme;
+
+// What a character means, the theme decides what that looks like.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    #[default]
+    Plain,
+    // Things in the background, like empty cells.
+    Dim,
+    // Things in the foreground, like lit pixels.
+    Bright,
+    // Something to point out, like the path in day 12.
+    Highlight,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Frame {
+    lines: Vec<Vec<(char, Style)>>,
+}
+
+impl Frame {
+    pub fn new() -> Self {
+        Frame {
+            lines: vec![vec![]],
+        }
+    }
+
+    pub fn push(&mut self, c: char, style: Style) {
+        // `new()` starts with an empty line, but `default()` doesn't.
+        if self.lines.is_empty() {
+            self.lines.push(vec![]);
+        }
+        self.lines.last_mut().unwrap().push((c, style));
+    }
+
+    pub fn push_str(&mut self, s: &str, style: Style) {
+        for c in s.chars() {
+            self.push(c, style);
+        }
+    }
+
+    pub fn newline(&mut self) {
+        self.lines.push(vec![]);
+    }
+
+    // A trailing empty line (after the last `newline()`) doesn't count.
+    pub fn lines(&self) -> &[Vec<(char, Style)>] {
+        match self.lines.split_last() {
+            Some((last, rest)) if last.is_empty() => rest,
+            _ => &self.lines,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.lines()
+            .iter()
+            .map(|line| line.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.lines().len()
+    }
+
+    // Renders the frame with the colours of `theme`, or as plain text. Every line ends with a
+    // newline, and no colour is left switched on at the end of a line.
+    pub fn render(&self, theme: Option<&Theme>) -> String {
+        let mut result = String::new();
+        for line in self.lines() {
+            let mut current = "";
+            for &(c, style) in line {
+                let code = theme.map_or("", |theme| theme.code(style));
+                if code != current {
+                    if !current.is_empty() {
+                        result.push_str("\x1B[0m");
+                    }
+                    if !code.is_empty() {
+                        result.push_str(&format!("\x1B[{code}m"));
+                    }
+                    current = code;
+                }
+                result.push(c);
+            }
+            if !current.is_empty() {
+                result.push_str("\x1B[0m");
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+// The plain text, without any colours.
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(None))
+    }
+}
+
+#[test]
+fn test_render() {
+    let mut frame = Frame::new();
+    frame.push_str("##", Style::Bright);
+    frame.push('.', Style::Dim);
+    frame.newline();
+    frame.push_str("ab", Style::Plain);
+    frame.newline();
+    assert_eq!((frame.width(), frame.height()), (3, 2));
+    assert_eq!(frame.to_string(), "##.\nab\n");
+    assert_eq!(
+        frame.render(Some(&crate::theme::THEMES[0])),
+        "\x1B[1m##\x1B[0m\x1B[2m.\x1B[0m\nab\n"
+    );
+}
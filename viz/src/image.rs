@@ -0,0 +1,235 @@
+// Pictures of the grids, for reports, or to compare renders in the tests. Unlike frames, images
+// have a colour per pixel and don't need a terminal at all.
+//
+// Images can be written in three formats:
+//
+// - PPM, the simplest image format there is: a tiny header, then the RGB values of all pixels.
+//   See https://netpbm.sourceforge.net/doc/ppm.html
+// - PNG, with the `png` crate, which every image viewer and browser can show.
+// - SVG, with one rectangle per run of same coloured pixels in a row. It scales without getting
+//   blurry.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    // The colour a fraction `t` (0.0 to 1.0) of the way from `self` to `other`.
+    pub fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> Rgb) -> Self {
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(f(x, y));
+            }
+        }
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    // Makes every pixel a `factor` x `factor` square. The grids are small, so one pixel per cell
+    // is hard to see.
+    pub fn scale(&self, factor: usize) -> Image {
+        Image::from_fn(self.width * factor, self.height * factor, |x, y| {
+            self.get(x / factor, y / factor)
+        })
+    }
+
+    // The binary variant of PPM ("P6"), with one byte per colour channel.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut result = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for pixel in &self.pixels {
+            result.extend([pixel.0, pixel.1, pixel.2]);
+        }
+        result
+    }
+
+    // Only reads what `to_ppm` writes (no comments in the header, 255 as the maximum value), which
+    // is enough to compare images in the tests.
+    pub fn from_ppm(data: &[u8]) -> Result<Image, String> {
+        let mut fields = vec![];
+        let mut start = 0;
+        // The header is four whitespace separated fields: "P6", width, height and 255.
+        while fields.len() < 4 {
+            let end = data[start..]
+                .iter()
+                .position(|b| b.is_ascii_whitespace())
+                .map(|i| start + i)
+                .ok_or("truncated PPM header")?;
+            fields.push(String::from_utf8_lossy(&data[start..end]).to_string());
+            start = end + 1;
+        }
+        if fields[0] != "P6" || fields[3] != "255" {
+            return Err("not a binary PPM with 8 bits per channel".to_string());
+        }
+        let width: usize = fields[1].parse().map_err(|_| "invalid width")?;
+        let height: usize = fields[2].parse().map_err(|_| "invalid height")?;
+        let pixels: Vec<Rgb> = data[start..]
+            .chunks_exact(3)
+            .map(|c| Rgb(c[0], c[1], c[2]))
+            .collect();
+        if pixels.len() != width * height {
+            return Err(format!(
+                "expected {} pixels, found {}",
+                width * height,
+                pixels.len()
+            ));
+        }
+        Ok(Image {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut result = vec![];
+        let mut encoder = png::Encoder::new(&mut result, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        let data: Vec<u8> = self.pixels.iter().flat_map(|p| [p.0, p.1, p.2]).collect();
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+        Ok(result)
+    }
+
+    pub fn to_svg(&self) -> String {
+        let mut result = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" \
+             viewBox=\"0 0 {0} {1}\" shape-rendering=\"crispEdges\">\n",
+            self.width, self.height
+        );
+        for y in 0..self.height {
+            let mut x = 0;
+            while x < self.width {
+                let colour = self.get(x, y);
+                let run = (x..self.width)
+                    .take_while(|&x2| self.get(x2, y) == colour)
+                    .count();
+                result.push_str(&format!(
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{run}\" height=\"1\" fill=\"{}\"/>\n",
+                    colour.hex()
+                ));
+                x += run;
+            }
+        }
+        result.push_str("</svg>\n");
+        result
+    }
+
+    // Writes the image in the format that matches the extension of `path`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        let data = match extension.to_ascii_lowercase().as_str() {
+            "ppm" => self.to_ppm(),
+            "png" => self.to_png().map_err(io::Error::other)?,
+            "svg" => self.to_svg().into_bytes(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unknown image format {extension:?}, use .ppm, .png or .svg"),
+                ))
+            }
+        };
+        fs::write(path, data)
+    }
+}
+
+#[cfg(test)]
+fn checkerboard() -> Image {
+    let black = Rgb(0, 0, 0);
+    let white = Rgb(255, 255, 255);
+    Image::from_fn(3, 2, |x, y| if (x + y) % 2 == 0 { black } else { white })
+}
+
+#[test]
+fn test_ppm() {
+    let image = checkerboard();
+    let ppm = image.to_ppm();
+    assert!(ppm.starts_with(b"P6\n3 2\n255\n\x00\x00\x00\xff\xff\xff"));
+    assert_eq!(ppm.len(), 11 + 3 * 2 * 3);
+    assert_eq!(Image::from_ppm(&ppm), Ok(image));
+    assert!(Image::from_ppm(b"P6\n3 2\n255\n\x00").is_err());
+}
+
+#[test]
+fn test_png() {
+    let png = checkerboard().to_png().unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+}
+
+#[test]
+fn test_svg() {
+    let image = Image::from_fn(3, 1, |x, _| Rgb(0, 0, if x < 2 { 0 } else { 255 }));
+    let svg = image.to_svg();
+    assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"2\" height=\"1\" fill=\"#000000\"/>"));
+    assert!(svg.contains("<rect x=\"2\" y=\"0\" width=\"1\" height=\"1\" fill=\"#0000ff\"/>"));
+}
+
+#[test]
+fn test_scale_and_mix() {
+    let image = checkerboard().scale(2);
+    assert_eq!((image.width(), image.height()), (6, 4));
+    assert_eq!(image.get(1, 1), Rgb(0, 0, 0));
+    assert_eq!(image.get(2, 1), Rgb(255, 255, 255));
+    assert_eq!(
+        Rgb(0, 100, 200).mix(Rgb(100, 100, 0), 0.5),
+        Rgb(50, 100, 100)
+    );
+}
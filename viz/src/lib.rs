@@ -0,0 +1,24 @@
+// Drawing things in the terminal: the CRT of day 10, the path of day 12, the rope of day 9.
+//
+// A solution builds a `Frame` (characters with a `Style` each) and hands it to a `Renderer`, which
+// takes care of the rest:
+//
+// - the styles are turned into colours by a `Theme` (or no colours at all when stdout is not a
+//   terminal, when `NO_COLOR` is set or with `--color never`),
+// - animations are shown at a fixed frame rate (`--fps`), by drawing every frame over the last
+//   one. When stdout is not a terminal only the last frame is printed,
+// - with `--record <path>` all frames are also written to an asciicast file, which can be replayed
+//   with `asciinema play <path>`.
+//
+// For pictures that don't need a terminal at all there is `Image` (see `image.rs`).
+
+pub mod asciicast;
+pub mod frame;
+pub mod image;
+pub mod renderer;
+pub mod theme;
+
+pub use frame::{Frame, Style};
+pub use image::{Image, Rgb};
+pub use renderer::{Colour, Options, Renderer};
+pub use theme::Theme;
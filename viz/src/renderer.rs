@@ -0,0 +1,348 @@
+// Shows frames in the terminal, and records them if asked to.
+
+use std::env;
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::mem;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::asciicast::Recorder;
+use crate::theme::{Theme, THEMES};
+use crate::Frame;
+
+// Clears the screen, and moves the cursor to the top left corner.
+const CLEAR: &str = "\x1B[2J";
+const HOME: &str = "\x1B[H";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    // Colours if stdout is a terminal and `NO_COLOR` isn't set (see https://no-color.org).
+    Auto,
+    Always,
+    Never,
+}
+
+pub struct Options {
+    pub fps: f64,
+    pub theme: &'static Theme,
+    pub colour: Colour,
+    pub record: Option<PathBuf>,
+}
+
+impl Options {
+    // Every animation has its own speed, so there's no default for the frame rate.
+    pub fn new(fps: f64) -> Self {
+        Options {
+            fps,
+            theme: &THEMES[0],
+            colour: Colour::Auto,
+            record: None,
+        }
+    }
+
+    // Takes the options for the renderer out of `args`, and returns the other arguments.
+    pub fn parse_args(
+        &mut self,
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<Vec<String>, String> {
+        let mut rest = vec![];
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--fps" => {
+                    let fps = value()?;
+                    // Anything slower than a frame every 100 seconds or faster than a terminal can
+                    // keep up with is a typo, and a tiny rate doesn't even fit into a `Duration`.
+                    self.fps = match fps.parse() {
+                        Ok(fps) if (0.01..=1000.0).contains(&fps) => fps,
+                        _ => return Err(format!("invalid frame rate: {fps}")),
+                    };
+                }
+                "--theme" => {
+                    let name = value()?;
+                    self.theme = Theme::get(&name).ok_or(format!("unknown theme: {name}"))?;
+                }
+                "--color" => {
+                    let colour = value()?;
+                    self.colour = match colour.as_str() {
+                        "auto" => Colour::Auto,
+                        "always" => Colour::Always,
+                        "never" => Colour::Never,
+                        _ => return Err(format!("invalid color mode: {colour}")),
+                    };
+                }
+                "--no-color" => self.colour = Colour::Never,
+                "--record" => self.record = Some(PathBuf::from(value()?)),
+                _ => rest.push(arg),
+            }
+        }
+        Ok(rest)
+    }
+
+    // The same for the command line arguments of a day binary (without the program name). Exits
+    // if there's an invalid option.
+    pub fn from_args(fps: f64) -> (Self, Vec<String>) {
+        let mut options = Options::new(fps);
+        match options.parse_args(env::args().skip(1)) {
+            Ok(rest) => (options, rest),
+            Err(msg) => {
+                let themes: Vec<&str> = THEMES.iter().map(|theme| theme.name).collect();
+                eprintln!("error: {msg}");
+                eprintln!(
+                    "options: [--fps <n>] [--theme <{}>] [--color <auto|always|never>] \
+                     [--no-color] [--record <path>]",
+                    themes.join("|")
+                );
+                process::exit(2);
+            }
+        }
+    }
+}
+
+// The recording file is created right away (so a wrong path fails early), but the header needs
+// the size of the frames, so it's only written with the first frame.
+enum Recording {
+    Off,
+    Pending(Box<dyn Write>),
+    On(Recorder<Box<dyn Write>>),
+}
+
+pub struct Renderer {
+    out: Box<dyn Write>,
+    terminal: bool,
+    colours: bool,
+    // A recording is always played back in a terminal, so it gets colours unless they were
+    // switched off explicitly.
+    record_colours: bool,
+    theme: &'static Theme,
+    frame_time: Duration,
+    last_frame: Option<Instant>,
+    // Whether the screen was already cleared for an animation.
+    animating: bool,
+    // The last frame of an animation, which is printed at the end when stdout is not a terminal.
+    pending: Option<Frame>,
+    recording: Recording,
+    // The time in the recording. It doesn't depend on how long rendering takes, so recordings
+    // always play back at the right frame rate.
+    clock: f64,
+}
+
+impl Renderer {
+    pub fn new(options: &Options) -> io::Result<Self> {
+        let recording = match &options.record {
+            Some(path) => Some(Box::new(BufWriter::new(File::create(path)?)) as Box<dyn Write>),
+            None => None,
+        };
+        let terminal = io::stdout().is_terminal();
+        Ok(Self::with_output(
+            Box::new(io::stdout()),
+            terminal,
+            recording,
+            options,
+        ))
+    }
+
+    // Like `new`, but writes to `out` (and `recording`) instead of stdout (and a file).
+    pub fn with_output(
+        out: Box<dyn Write>,
+        terminal: bool,
+        recording: Option<Box<dyn Write>>,
+        options: &Options,
+    ) -> Self {
+        let colours = match options.colour {
+            Colour::Auto => terminal && env::var_os("NO_COLOR").is_none(),
+            Colour::Always => true,
+            Colour::Never => false,
+        };
+        Renderer {
+            out,
+            terminal,
+            colours,
+            record_colours: options.colour != Colour::Never,
+            theme: options.theme,
+            frame_time: Duration::from_secs_f64(1.0 / options.fps),
+            last_frame: None,
+            animating: false,
+            pending: None,
+            recording: recording.map_or(Recording::Off, Recording::Pending),
+            clock: 0.0,
+        }
+    }
+
+    fn theme(&self) -> Option<&'static Theme> {
+        self.colours.then_some(self.theme)
+    }
+
+    // Shows a single frame, like a picture.
+    pub fn show(&mut self, frame: &Frame) -> io::Result<()> {
+        self.print_pending()?;
+        self.animating = false;
+        write!(self.out, "{}", frame.render(self.theme()))?;
+        self.out.flush()?;
+        self.record(frame, "")?;
+        // Stay on a picture in the recording for a second.
+        self.clock += 1.0;
+        Ok(())
+    }
+
+    // Shows the next frame of an animation, drawn over the previous one. Waits until it's time
+    // for the next frame.
+    pub fn animate(&mut self, frame: &Frame) -> io::Result<()> {
+        let prefix = if self.animating {
+            HOME.to_string()
+        } else {
+            format!("{CLEAR}{HOME}")
+        };
+        self.animating = true;
+        if self.terminal {
+            if let Some(last) = self.last_frame {
+                let next = last + self.frame_time;
+                let now = Instant::now();
+                if next > now {
+                    thread::sleep(next - now);
+                }
+            }
+            self.last_frame = Some(Instant::now());
+            write!(self.out, "{prefix}{}", frame.render(self.theme()))?;
+            self.out.flush()?;
+        } else {
+            self.pending = Some(frame.clone());
+        }
+        self.record(frame, &prefix)?;
+        self.clock += self.frame_time.as_secs_f64();
+        Ok(())
+    }
+
+    fn print_pending(&mut self) -> io::Result<()> {
+        if let Some(frame) = self.pending.take() {
+            write!(self.out, "{}", frame.render(self.theme()))?;
+            self.out.flush()?;
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, frame: &Frame, prefix: &str) -> io::Result<()> {
+        if let Recording::Pending(_) = self.recording {
+            if let Recording::Pending(out) = mem::replace(&mut self.recording, Recording::Off) {
+                let recorder = Recorder::new(out, frame.width(), frame.height())?;
+                self.recording = Recording::On(recorder);
+            }
+        }
+        if let Recording::On(recorder) = &mut self.recording {
+            let theme = self.record_colours.then_some(self.theme);
+            // asciinema replays in a real terminal, where a newline only moves the cursor down.
+            let data = frame.render(theme).replace('\n', "\r\n");
+            recorder.output(self.clock, &(prefix.to_string() + &data))?;
+        }
+        Ok(())
+    }
+
+    // Prints the last frame of an animation if it wasn't shown yet, and finishes the recording.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.print_pending()?;
+        if let Recording::On(recorder) = self.recording {
+            recorder.into_inner().flush()?;
+        } else if let Recording::Pending(mut out) = self.recording {
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+// Collects what the renderer writes, so the tests can look at it afterwards.
+#[cfg(test)]
+#[derive(Clone, Default)]
+struct Buffer(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+#[cfg(test)]
+impl Write for Buffer {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(data)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl Buffer {
+    fn text(&self) -> String {
+        String::from_utf8(self.0.borrow().clone()).unwrap()
+    }
+}
+
+#[cfg(test)]
+fn frames() -> Vec<Frame> {
+    ["#..", ".#.", "..#"]
+        .iter()
+        .map(|s| {
+            let mut frame = Frame::new();
+            frame.push_str(s, crate::Style::Bright);
+            frame
+        })
+        .collect()
+}
+
+#[test]
+fn test_animate_in_terminal() {
+    let out = Buffer::default();
+    let mut options = Options::new(1000.0);
+    options.colour = Colour::Never;
+    let mut renderer = Renderer::with_output(Box::new(out.clone()), true, None, &options);
+    for frame in frames() {
+        renderer.animate(&frame).unwrap();
+    }
+    renderer.finish().unwrap();
+    assert_eq!(out.text(), "\x1B[2J\x1B[H#..\n\x1B[H.#.\n\x1B[H..#\n");
+}
+
+#[test]
+fn test_animate_not_in_terminal() {
+    // Only the last frame is printed, without colours, but everything is recorded.
+    let out = Buffer::default();
+    let recording = Buffer::default();
+    let options = Options::new(10.0);
+    let mut renderer = Renderer::with_output(
+        Box::new(out.clone()),
+        false,
+        Some(Box::new(recording.clone())),
+        &options,
+    );
+    for frame in frames() {
+        renderer.animate(&frame).unwrap();
+    }
+    assert_eq!(out.text(), "");
+    renderer.show(&frames()[0]).unwrap();
+    renderer.finish().unwrap();
+    assert_eq!(out.text(), "..#\n#..\n");
+    assert_eq!(
+        recording.text(),
+        "{\"version\": 2, \"width\": 3, \"height\": 1}\n\
+         [0, \"o\", \"\\u001b[2J\\u001b[H\\u001b[1m#..\\u001b[0m\\r\\n\"]\n\
+         [0.1, \"o\", \"\\u001b[H\\u001b[1m.#.\\u001b[0m\\r\\n\"]\n\
+         [0.2, \"o\", \"\\u001b[H\\u001b[1m..#\\u001b[0m\\r\\n\"]\n\
+         [0.3, \"o\", \"\\u001b[1m#..\\u001b[0m\\r\\n\"]\n"
+    );
+}
+
+#[test]
+fn test_parse_args() {
+    let args = ["input.txt", "--fps", "30", "--theme", "amber", "--no-color"];
+    let mut options = Options::new(60.0);
+    let rest = options.parse_args(args.map(String::from)).unwrap();
+    assert_eq!(rest, ["input.txt"]);
+    assert_eq!(options.fps, 30.0);
+    assert_eq!(options.theme.name, "amber");
+    assert_eq!(options.colour, Colour::Never);
+    for fps in ["0", "1e-300", "1e300", "NaN", "inf"] {
+        assert!(options
+            .parse_args(["--fps".to_string(), fps.to_string()])
+            .is_err());
+    }
+    assert!(options.parse_args(["--theme".to_string()]).is_err());
+}
@@ -0,0 +1,64 @@
+// Colour themes. A theme maps every `Style` to the parameters of an ANSI "Select Graphic
+// Rendition" escape sequence (`\x1B[<code>m`), an empty code means no escape sequence at all.
+// See https://en.wikipedia.org/wiki/ANSI_escape_code#SGR_(Select_Graphic_Rendition)_parameters
+//
+// Rust does not support octal escape codes, but only hexadecimal ones, so you have to
+// start the escape sequences with \x1B instead of \033 as you see in other languages.
+
+use crate::Style;
+
+#[derive(Debug)]
+pub struct Theme {
+    pub name: &'static str,
+    plain: &'static str,
+    dim: &'static str,
+    bright: &'static str,
+    highlight: &'static str,
+}
+
+impl Theme {
+    pub fn code(&self, style: Style) -> &'static str {
+        match style {
+            Style::Plain => self.plain,
+            Style::Dim => self.dim,
+            Style::Bright => self.bright,
+            Style::Highlight => self.highlight,
+        }
+    }
+
+    pub fn get(name: &str) -> Option<&'static Theme> {
+        THEMES.iter().find(|theme| theme.name == name)
+    }
+}
+
+pub const THEMES: [Theme; 3] = [
+    // The terminal's own colours, only bold and faint.
+    Theme {
+        name: "default",
+        plain: "",
+        dim: "2",
+        bright: "1",
+        highlight: "1;33",
+    },
+    // Like an old green phosphor monitor.
+    Theme {
+        name: "green",
+        plain: "32",
+        dim: "2;32",
+        bright: "1;92",
+        highlight: "1;97",
+    },
+    Theme {
+        name: "amber",
+        plain: "33",
+        dim: "2;33",
+        bright: "1;93",
+        highlight: "1;97",
+    },
+];
+
+#[test]
+fn test_get() {
+    assert_eq!(Theme::get("green").unwrap().code(Style::Bright), "1;92");
+    assert!(Theme::get("purple").is_none());
+}